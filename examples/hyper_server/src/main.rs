@@ -1,3 +1,4 @@
+use route_match::response;
 use route_match::route;
 use std::net::SocketAddr;
 
@@ -18,7 +19,7 @@ async fn serve_response(code: u16, text: &str) -> Result<Response<String>, Err>
 
 async fn on_request(request: Request<hyper::body::Incoming>) -> Result<Response<String>, Err> {
     route! {
-        match (&request.method().as_str(), &request.uri().path()) {
+        match request {
             GET /echo/:message => serve_response(200, message).await,
             GET /double/:number => {
                 let Ok(x) = number.parse::<f64>() else {
@@ -37,7 +38,7 @@ async fn on_request(request: Request<hyper::body::Incoming>) -> Result<Response<
             _ /any_method => serve_response(200, "any_method ").await
             OPTIONS _ => serve_response(200, "any_path ").await
             :method _ => serve_response(200, &format!("method: {method}, any_path ")).await
-            _ => serve_response(404, "not found").await
+            _ => Ok(response::not_found())
         }
     }
 }
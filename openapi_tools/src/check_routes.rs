@@ -0,0 +1,189 @@
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use openapiv3::{OpenAPI, RefOr};
+
+use crate::{
+    CheckRoutesArgs,
+    extract::{
+        error_location,
+        extract_impl::{ExtractedRoute, OPERATION_METHODS},
+        extract_sources,
+    },
+    utils::load_spec,
+};
+
+/// A difference between the operations described by a spec and the routes
+/// defined in the code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    /// An operation in the spec which no route matches
+    MissingRoute { method: String, path: String },
+    /// A route with no operation in the spec
+    UndocumentedRoute { pattern: String, location: String },
+    /// An operation whose path parameters are named differently by the route
+    /// which matches it, e.g. `{userId}` and `:id`
+    ParameterMismatch {
+        method: String,
+        path: String,
+        pattern: String,
+        location: String,
+    },
+    /// A route block which couldn't be parsed, or a mount whose router
+    /// couldn't be found, so that its routes weren't checked
+    Unchecked { location: String, message: String },
+}
+
+impl Display for Drift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Drift::MissingRoute { method, path } => {
+                write!(f, "{} {}: no route matches this operation", method, path)
+            }
+            Drift::UndocumentedRoute { pattern, location } => {
+                write!(
+                    f,
+                    "{}: `{}` has no operation in the spec",
+                    location, pattern
+                )
+            }
+            Drift::ParameterMismatch {
+                method,
+                path,
+                pattern,
+                location,
+            } => write!(
+                f,
+                "{}: `{}` names its parameters differently to {} {} in the spec",
+                location, pattern, method, path
+            ),
+            Drift::Unchecked { location, message } => {
+                write!(f, "{}: routes weren't checked: {}", location, message)
+            }
+        }
+    }
+}
+
+/// Checks the routes in the sources against the spec.  Sources which can't be
+/// fully extracted are reported as [`Drift::Unchecked`], so that they fail
+/// the check rather than pass it with routes missing.
+pub fn exec(args: CheckRoutesArgs) -> Vec<Drift> {
+    let spec = load_spec(&args.spec);
+    let extraction = extract_sources(&args.src, args.verbose);
+    let mut drift: Vec<Drift> = extraction
+        .errors
+        .iter()
+        .map(|(file, error)| Drift::Unchecked {
+            location: error_location(file, error),
+            message: error.to_string(),
+        })
+        .collect();
+    drift.extend(check(&spec, &extraction.routes));
+    drift
+}
+
+/// Compares the operations in a spec with the routes found in the sources.
+///
+/// Paths are compared with their parameters erased, so `/user/{userId}` is
+/// matched by `GET /user/:id`, and then reported if the names differ.  An
+/// operation is matched by the first route for its method and path, as a
+/// request would be.  Routes for methods which OpenAPI has no operation for
+/// aren't checked.
+pub fn check(spec: &OpenAPI, routes: &[(PathBuf, ExtractedRoute)]) -> Vec<Drift> {
+    let operations = spec_operations(spec);
+    let mut drift = vec![];
+
+    let mut mismatched = BTreeSet::new();
+    for (method, path) in &operations {
+        let shape = path_shape(path);
+        let matched = routes.iter().enumerate().find(|(_, (_, route))| {
+            path_shape(&route.path) == shape && route.method.as_ref().is_none_or(|m| m == method)
+        });
+        let Some((index, (file, route))) = matched else {
+            drift.push(Drift::MissingRoute {
+                method: method.clone(),
+                path: path.clone(),
+            });
+            continue;
+        };
+        if parameter_names(path) != parameter_names(&route.path) && mismatched.insert((index, path))
+        {
+            drift.push(Drift::ParameterMismatch {
+                method: method.clone(),
+                path: path.clone(),
+                pattern: route.pattern.clone(),
+                location: location(file, route),
+            });
+        }
+    }
+
+    for (file, route) in routes {
+        let shape = path_shape(&route.path);
+        let documented = match &route.method {
+            Some(method) if !OPERATION_METHODS.contains(&method.as_str()) => continue,
+            Some(method) => operations
+                .iter()
+                .any(|(m, path)| m == method && path_shape(path) == shape),
+            None => operations.iter().any(|(_, path)| path_shape(path) == shape),
+        };
+        if !documented {
+            drift.push(Drift::UndocumentedRoute {
+                pattern: route.pattern.clone(),
+                location: location(file, route),
+            });
+        }
+    }
+
+    drift
+}
+
+/// The method and path of each operation in the spec, e.g. `("GET", "/user/{id}")`
+fn spec_operations(spec: &OpenAPI) -> Vec<(String, String)> {
+    let mut operations = vec![];
+    for (path, item) in spec.paths.iter() {
+        let RefOr::Item(item) = item else {
+            continue;
+        };
+        for (method, _) in item.iter() {
+            operations.push((method.to_uppercase(), path.clone()));
+        }
+    }
+    operations
+}
+
+/// The path with the names of its parameters erased, e.g. `/user/{}`.  A
+/// trailing `/` is ignored, since `route!` ignores empty segments.
+fn path_shape(path: &str) -> String {
+    let mut shape = String::new();
+    let mut in_parameter = false;
+    for c in path.chars() {
+        match c {
+            '{' => {
+                in_parameter = true;
+                shape.push_str("{}");
+            }
+            '}' => in_parameter = false,
+            c if !in_parameter => shape.push(c),
+            _ => {}
+        }
+    }
+    match shape.trim_end_matches('/') {
+        "" => "/".to_string(),
+        shape => shape.to_string(),
+    }
+}
+
+fn parameter_names(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}'))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+fn location(file: &Path, route: &ExtractedRoute) -> String {
+    format!("{}:{}", file.display(), route.line)
+}
@@ -0,0 +1,19 @@
+pub mod rust_server;
+
+use crate::{CodegenArgs, CodegenTarget, utils::load_spec};
+
+pub fn exec(args: CodegenArgs) -> Option<String> {
+    let (code, output) = match args.target {
+        CodegenTarget::RustServer(args) => {
+            let spec = load_spec(&args.spec);
+            (rust_server::generate(&spec), args.output)
+        }
+    };
+
+    if let Some(path) = &output {
+        std::fs::write(path, code).unwrap();
+        return None;
+    }
+
+    Some(code)
+}
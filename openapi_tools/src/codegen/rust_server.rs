@@ -0,0 +1,305 @@
+use std::{collections::BTreeSet, fmt::Write};
+
+use heck::ToSnakeCase;
+use openapiv3::{
+    IntegerFormat, NumberFormat, OpenAPI, Operation, Parameter, PathItem, RefOr, SchemaKind, Type,
+    VariantOrUnknownOrEmpty,
+};
+
+use crate::route_pattern::{convert_path, identifier, path_parameters, template_param};
+
+/// The names used by the generated dispatch function, which captures and
+/// handler methods must not shadow or collide with
+const RESERVED_NAMES: &[&str] = &["handlers", "req", "not_found"];
+
+/// An operation in the spec, along with its handler method and `route!` arm
+struct Handler {
+    name: String,
+    method: String,
+    path: String,
+    pattern: String,
+    summary: Option<String>,
+    description: Option<String>,
+    params: Vec<PathParam>,
+    /// The capture bound with `|name|` for a `.{json,csv}` extension
+    extension: Option<String>,
+}
+
+/// A value captured from the path, in the order it appears in the path
+struct PathParam {
+    /// The name bound by the arm's pattern
+    binding: String,
+    /// The name of the handler's argument
+    arg: String,
+    /// The type the capture is parsed to, or `None` if it's passed as a `&str`
+    ty: Option<&'static str>,
+}
+
+/// Generates a module with an `ApiHandlers` trait, which has a method for each
+/// operation in the spec, and a `dispatch` function which routes requests to
+/// them with `route!`.  Operations whose paths can't be written as a `route!`
+/// pattern are reported on stderr and skipped.
+pub fn generate(spec: &OpenAPI) -> String {
+    let mut names: BTreeSet<String> = RESERVED_NAMES.iter().map(|name| name.to_string()).collect();
+    let mut handlers = vec![];
+    for (path, item) in spec.paths.iter() {
+        let RefOr::Item(item) = item else {
+            eprintln!("warning: {}: path item references aren't supported", path);
+            continue;
+        };
+        for (method, operation) in item.iter() {
+            let method = method.to_uppercase();
+            match handler(spec, path, &method, item, operation, &mut names) {
+                Ok(handler) => handlers.push(handler),
+                Err(message) => eprintln!("warning: {} {}: {}", method, path, message),
+            }
+        }
+    }
+    render(spec, &handlers)
+}
+
+fn handler(
+    spec: &OpenAPI,
+    path: &str,
+    method: &str,
+    item: &PathItem,
+    operation: &Operation,
+    names: &mut BTreeSet<String>,
+) -> Result<Handler, String> {
+    let parameters = path_parameters(spec, item, &[operation])?;
+    let conversion = convert_path(spec, path, &parameters, RESERVED_NAMES)?;
+
+    let mut args: BTreeSet<String> = BTreeSet::from(["req".to_string()]);
+    let params = conversion
+        .captures
+        .iter()
+        .map(|capture| PathParam {
+            binding: capture.binding.clone(),
+            arg: unique(identifier(&capture.name.to_snake_case()), &mut args),
+            ty: parameters
+                .iter()
+                .find(|parameter| parameter.name == capture.name && !capture.extension)
+                .and_then(|parameter| parsed_type(spec, parameter)),
+        })
+        .collect();
+    let extension = conversion
+        .captures
+        .iter()
+        .find(|capture| capture.extension)
+        .map(|capture| capture.binding.clone());
+
+    let summary = operation
+        .summary
+        .as_ref()
+        .map(|summary| summary.trim().to_string())
+        .filter(|summary| !summary.is_empty());
+    let description = operation
+        .description
+        .as_ref()
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty() && Some(description) != summary.as_ref());
+
+    Ok(Handler {
+        name: unique(identifier(&operation_name(method, path, operation)), names),
+        method: method.to_string(),
+        path: path.to_string(),
+        pattern: conversion.pattern,
+        summary,
+        description,
+        params,
+        extension,
+    })
+}
+
+fn unique(name: String, names: &mut BTreeSet<String>) -> String {
+    let mut unique = name.clone();
+    let mut n = 1;
+    while names.contains(&unique) {
+        n += 1;
+        unique = format!("{}_{}", name, n);
+    }
+    names.insert(unique.clone());
+    unique
+}
+
+/// The `operationId` in snake case, or a name made from the method and path,
+/// e.g. `get_users_by_id` for `GET /users/{id}`
+fn operation_name(method: &str, path: &str, operation: &Operation) -> String {
+    if let Some(operation_id) = &operation.operation_id {
+        return operation_id.to_snake_case();
+    }
+    let mut words = vec![method.to_string()];
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        match template_param(segment) {
+            Some(name) => words.push(format!("by_{}", name)),
+            None => words.push(segment.to_string()),
+        }
+    }
+    words.join("_").to_snake_case()
+}
+
+/// The type a path parameter is parsed to, if its schema isn't a string
+fn parsed_type(spec: &OpenAPI, parameter: &Parameter) -> Option<&'static str> {
+    let schema = parameter.schema()?.resolve(spec);
+    match &schema.kind {
+        SchemaKind::Type(Type::Integer(integer)) => match integer.format {
+            VariantOrUnknownOrEmpty::Item(IntegerFormat::Int32) => Some("i32"),
+            _ => Some("i64"),
+        },
+        SchemaKind::Type(Type::Number(number)) => match number.format {
+            VariantOrUnknownOrEmpty::Item(NumberFormat::Float) => Some("f32"),
+            _ => Some("f64"),
+        },
+        SchemaKind::Type(Type::Boolean {}) => Some("bool"),
+        _ => None,
+    }
+}
+
+fn render(spec: &OpenAPI, handlers: &[Handler]) -> String {
+    let mut code = String::new();
+    let title = spec.info.title.trim();
+    let _ = writeln!(
+        code,
+        "// Generated by `openapi_tools codegen rust-server` from {} {}",
+        title, spec.info.version
+    );
+    code.push('\n');
+
+    let _ = writeln!(
+        code,
+        "/// Handles the operations of {}, with a method for each operation",
+        title
+    );
+    code.push_str("pub trait ApiHandlers {\n");
+    code.push_str("    /// The request passed on to each handler\n");
+    code.push_str("    type Request;\n");
+    code.push_str("    /// The response returned by each handler\n");
+    code.push_str("    type Response;\n");
+    for handler in handlers {
+        code.push('\n');
+        let mut docs: Vec<&str> = vec![];
+        if let Some(summary) = &handler.summary {
+            docs.extend(summary.lines());
+            docs.push("");
+        }
+        if let Some(description) = &handler.description {
+            docs.extend(description.lines());
+            docs.push("");
+        }
+        let operation = format!("`{} {}`", handler.method, handler.path);
+        docs.push(&operation);
+        write_docs(&mut code, "    ", &docs);
+
+        let args: Vec<String> = handler
+            .params
+            .iter()
+            .map(|param| format!(", {}: {}", param.arg, param.ty.unwrap_or("&str")))
+            .collect();
+        let _ = writeln!(
+            code,
+            "    fn {}(&self, req: &Self::Request{}) -> Self::Response;",
+            handler.name,
+            args.concat()
+        );
+    }
+    code.push('\n');
+    code.push_str("    /// Called for requests which don't match any operation\n");
+    code.push_str("    fn not_found(&self, req: &Self::Request) -> Self::Response;\n");
+    code.push_str("}\n\n");
+
+    code.push_str("/// Calls the handler for the operation which matches the request, or\n");
+    code.push_str("/// `not_found` if there isn't one.  A request doesn't match an operation\n");
+    code.push_str("/// if a path parameter can't be parsed to the type in the spec.\n");
+    let snake_case = handlers
+        .iter()
+        .flat_map(|handler| &handler.params)
+        .map(|param| param.binding.trim_start_matches("r#"))
+        .all(|binding| binding == binding.to_snake_case());
+    if !snake_case {
+        code.push_str("#[allow(non_snake_case)]\n");
+    }
+    code.push_str("pub fn dispatch<H: ApiHandlers>(\n");
+    code.push_str("    handlers: &H,\n");
+    code.push_str("    method: &str,\n");
+    code.push_str("    path: &str,\n");
+    code.push_str("    req: &H::Request,\n");
+    code.push_str(") -> H::Response {\n");
+    code.push_str("    ::route_match::route! {\n");
+    code.push_str("        #![order = specific]\n");
+    code.push_str("        match (&method, &path) {\n");
+    for handler in handlers {
+        if let Some(summary) = &handler.summary {
+            write_docs(
+                &mut code,
+                "            ",
+                &summary.lines().collect::<Vec<_>>(),
+            );
+        }
+        let _ = writeln!(
+            code,
+            "            {} {} => {},",
+            handler.method,
+            handler.pattern,
+            arm_expr(handler)
+        );
+    }
+    code.push_str("            _ => handlers.not_found(req),\n");
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push_str("}\n");
+    code
+}
+
+/// Calls the handler, in a `try` arm which parses the captures if any of them
+/// aren't strings
+fn arm_expr(handler: &Handler) -> String {
+    let args: Vec<&str> = std::iter::once("req")
+        .chain(handler.params.iter().map(|param| param.binding.as_str()))
+        .collect();
+    let call = format!("handlers.{}({})", handler.name, args.join(", "));
+    let extension = match &handler.extension {
+        Some(binding) => format!("|{}| ", binding),
+        None => String::new(),
+    };
+
+    let parsed: Vec<&str> = handler
+        .params
+        .iter()
+        .filter(|param| param.ty.is_some())
+        .map(|param| param.binding.as_str())
+        .collect();
+    let expr = match parsed.as_slice() {
+        [] => return format!("{}{}", extension, call),
+        [binding] => format!(
+            "match {binding}.parse() {{\n                Ok({binding}) => Some({call}),\n                Err(_) => None,\n            }}"
+        ),
+        bindings => {
+            let parses: Vec<String> = bindings
+                .iter()
+                .map(|binding| format!("{}.parse()", binding))
+                .collect();
+            let oks: Vec<String> = bindings
+                .iter()
+                .map(|binding| format!("Ok({})", binding))
+                .collect();
+            format!(
+                "match ({}) {{\n                ({}) => Some({}),\n                _ => None,\n            }}",
+                parses.join(", "),
+                oks.join(", "),
+                call
+            )
+        }
+    };
+    format!("try {}{}", extension, expr)
+}
+
+fn write_docs(code: &mut String, indent: &str, lines: &[&str]) {
+    for line in lines {
+        let line = line.trim_end();
+        if line.is_empty() {
+            let _ = writeln!(code, "{}///", indent);
+        } else {
+            let _ = writeln!(code, "{}/// {}", indent, line);
+        }
+    }
+}
@@ -0,0 +1,546 @@
+use openapiv3::{Operation, Parameter, PathItem, Paths, RefOr, Schema};
+use route_match_syntax::{
+    match_stmnt::{MatchArm, MatchStmnt},
+    method::Method,
+    path::PathComponent,
+    route::{ArmKind, Route},
+    router::RouterDef,
+};
+use syn::{Expr, ItemMod, Macro, ext::IdentExt, visit::Visit};
+
+/// The methods which have an operation in an OpenAPI path item
+pub const OPERATION_METHODS: &[&str] = &[
+    "GET", "PUT", "POST", "DELETE", "OPTIONS", "HEAD", "PATCH", "TRACE",
+];
+
+/// A route found in a `route!` block, translated to OpenAPI terms
+#[derive(Debug, Clone)]
+pub struct ExtractedRoute {
+    /// The pattern as it's written in the arm, e.g. `GET /user/:id`
+    pub pattern: String,
+    /// The path as an OpenAPI template, e.g. `/user/{id}`
+    pub path: String,
+    /// The method the route matches, or `None` if it matches any method
+    pub method: Option<String>,
+    pub parameters: Vec<Parameter>,
+    /// The doc comments written on the arm
+    pub docs: Vec<String>,
+    /// The line of the route pattern in its source file
+    pub line: usize,
+}
+
+/// Finds the routes defined with `route!`, `route_opt!` and `router!` in a
+/// source file, along with any invocations which couldn't be parsed.
+///
+/// Arms which can't be described by an OpenAPI path are skipped: the default
+/// arm, `_` paths and `use` arms.  See [`resolve_routes`] for mounted routers.
+pub fn extract_routes(source: &str) -> (Vec<ExtractedRoute>, Vec<syn::Error>) {
+    let extraction = resolve_routes(&[((), parse_routes(source))]);
+    (
+        extraction
+            .routes
+            .into_iter()
+            .map(|(_, route)| route)
+            .collect(),
+        extraction
+            .errors
+            .into_iter()
+            .map(|(_, error)| error)
+            .collect(),
+    )
+}
+
+/// The route blocks in a source file, and the invocations which couldn't be
+/// parsed
+#[derive(Debug, Default)]
+pub struct SourceRoutes {
+    /// The path of the module the file declares, e.g. `["api", "v1"]`, if
+    /// it's known
+    pub module: Vec<String>,
+    pub blocks: Vec<RouteBlock>,
+    pub errors: Vec<syn::Error>,
+}
+
+/// The route arms of a `route!`, `route_opt!` or `router!` block
+#[derive(Debug)]
+pub struct RouteBlock {
+    /// The name of the function defined by a `router!`
+    pub router: Option<String>,
+    /// The inline modules the block is in, within its file
+    pub module: Vec<String>,
+    pub arms: Vec<Route>,
+}
+
+pub fn parse_routes(source: &str) -> SourceRoutes {
+    let mut visitor = RouteVisitor::default();
+    match syn::parse_file(source) {
+        Ok(file) => visitor.visit_file(&file),
+        Err(error) => visitor.source.errors.push(error),
+    }
+    visitor.source
+}
+
+/// Extracts the routes of the parsed sources, each keyed by its file.
+///
+/// The arms of a router which is mounted by a `mount` arm are extracted under
+/// the prefix of the mount, e.g. `POST /orders` mounted by `_ /api/..` as
+/// `POST /api/orders`, and those of routers which aren't mounted as they are.
+///
+/// Routers are found by the module path of the mounted router, which is
+/// matched against the modules the `router!` blocks are declared in, as far
+/// as they're known.  A `mount` arm whose router isn't in the sources, or
+/// which could be more than one of them, is reported, as are parse errors.
+pub fn resolve_routes<F: Clone>(sources: &[(F, SourceRoutes)]) -> Extraction<F> {
+    let mut resolver = Resolver {
+        sources,
+        routes: vec![],
+        errors: vec![],
+    };
+    let mut mounted: Vec<BlockId> = vec![];
+    for (_, source) in sources {
+        for block in &source.blocks {
+            let module = source.module.iter().chain(&block.module);
+            let module: Vec<&str> = module.map(String::as_str).collect();
+            mounted.extend(
+                block
+                    .arms
+                    .iter()
+                    .filter(|route| route.kind == ArmKind::Mount)
+                    .filter_map(|route| resolver.router(&route.expr, &module).ok()),
+            );
+        }
+    }
+
+    for (source_index, (file, source)) in sources.iter().enumerate() {
+        resolver.errors.extend(
+            source
+                .errors
+                .iter()
+                .map(|error| (file.clone(), error.clone())),
+        );
+        for (block_index, block) in source.blocks.iter().enumerate() {
+            let id = (source_index, block_index);
+            if mounted.contains(&id) {
+                continue;
+            }
+            for route in &block.arms {
+                resolver.extract(id, route, &Mount::default(), &[]);
+            }
+        }
+    }
+    Extraction {
+        routes: resolver.routes,
+        errors: resolver.errors,
+    }
+}
+
+/// The routes extracted from parsed sources and the errors found in them, each
+/// along with its file
+#[derive(Debug)]
+pub struct Extraction<F> {
+    pub routes: Vec<(F, ExtractedRoute)>,
+    pub errors: Vec<(F, syn::Error)>,
+}
+
+struct Resolver<'a, F> {
+    sources: &'a [(F, SourceRoutes)],
+    routes: Vec<(F, ExtractedRoute)>,
+    errors: Vec<(F, syn::Error)>,
+}
+
+/// A route block, by the index of its source and its index in the source
+type BlockId = (usize, usize);
+
+/// The path prefix and method of the `mount` arms a router's arms are reached
+/// through
+#[derive(Default)]
+struct Mount<'a> {
+    prefix: Vec<&'a PathComponent>,
+    method: Option<&'a Method>,
+}
+
+impl<'a, F: Clone> Resolver<'a, F> {
+    /// The module path of a block, followed by the name of its router
+    fn path(&self, (source, block): BlockId) -> Vec<&'a str> {
+        let source = &self.sources[source].1;
+        let block = &source.blocks[block];
+        source
+            .module
+            .iter()
+            .chain(&block.module)
+            .chain(&block.router)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The `router!` mounted by `expr`, from a block in `module`.  The
+    /// router whose path shares the most trailing segments with the mounted
+    /// path is taken, since a file's module isn't always known, and one at
+    /// the path as it's resolved from `module` settles a tie.
+    fn router(&self, expr: &Expr, module: &[&str]) -> Result<BlockId, String> {
+        let (written, resolved) = mount_target(expr, module).unwrap_or_default();
+        let mut best = 0;
+        let mut candidates: Vec<BlockId> = vec![];
+        for (source_index, (_, source)) in self.sources.iter().enumerate() {
+            for (block_index, block) in source.blocks.iter().enumerate() {
+                if block.router.is_none() {
+                    continue;
+                }
+                let id = (source_index, block_index);
+                let path = self.path(id);
+                let shared = path
+                    .iter()
+                    .rev()
+                    .zip(written.iter().rev())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                // Every segment of the shorter path has to match
+                if shared == 0 || shared < path.len().min(written.len()) || shared < best {
+                    continue;
+                }
+                if shared > best {
+                    best = shared;
+                    candidates.clear();
+                }
+                candidates.push(id);
+            }
+        }
+        if candidates.len() > 1 {
+            let exact: Vec<BlockId> = candidates
+                .iter()
+                .copied()
+                .filter(|&id| self.path(id) == resolved)
+                .collect();
+            if exact.len() == 1 {
+                candidates = exact;
+            }
+        }
+        match candidates.as_slice() {
+            [] => Err(
+                "the mounted routes aren't extracted, since there's no `router!` with this name in the sources"
+                    .to_string(),
+            ),
+            [id] => Ok(*id),
+            _ => {
+                let paths: Vec<String> = candidates
+                    .iter()
+                    .map(|&id| format!("`{}`", self.path(id).join("::")))
+                    .collect();
+                Err(format!(
+                    "the mounted routes aren't extracted, since more than one `router!` in the sources could be mounted here: {}",
+                    paths.join(", ")
+                ))
+            }
+        }
+    }
+
+    fn extract(&mut self, at: BlockId, route: &'a Route, mount: &Mount<'a>, routers: &[BlockId]) {
+        let file = &self.sources[at.0].0;
+        if route.kind != ArmKind::Mount {
+            self.routes
+                .extend(extract_route(route, mount).map(|extracted| (file.clone(), extracted)));
+            return;
+        }
+
+        let module = self.path(at);
+        let module = match self.sources[at.0].1.blocks[at.1].router {
+            Some(_) => &module[..module.len() - 1],
+            None => &module[..],
+        };
+        let router = match self.router(&route.expr, module) {
+            Ok(router) => router,
+            Err(message) => {
+                self.errors
+                    .push((file.clone(), syn::Error::new_spanned(&route.expr, message)));
+                return;
+            }
+        };
+        if routers.contains(&router) || router == at {
+            self.errors.push((
+                file.clone(),
+                syn::Error::new_spanned(
+                    &route.expr,
+                    format!(
+                        "`{}` is mounted inside itself",
+                        self.path(router).join("::")
+                    ),
+                ),
+            ));
+            return;
+        }
+
+        // A mount for one method only reaches the arms which match it
+        let method = match (method_name(&route.method), mount.method) {
+            (Some(inner), Some(outer))
+                if method_name(outer).is_some_and(|outer| outer != inner) =>
+            {
+                return;
+            }
+            (Some(_), _) => Some(&route.method),
+            (None, outer) => outer,
+        };
+        let components = &route.path.components;
+        let nested = Mount {
+            prefix: mount
+                .prefix
+                .iter()
+                .copied()
+                .chain(&components[..components.len() - 1])
+                .collect(),
+            method,
+        };
+        let routers: Vec<BlockId> = routers.iter().copied().chain([at]).collect();
+        for arm in &self.sources[router.0].1.blocks[router.1].arms {
+            self.extract(router, arm, &nested, &routers);
+        }
+    }
+}
+
+/// The path of the router a `mount` arm mounts as it's written, e.g.
+/// `["api", "route"]` for `api::route`, along with the path it resolves to
+/// from `module`
+fn mount_target(expr: &Expr, module: &[&str]) -> Option<(Vec<String>, Vec<String>)> {
+    let Expr::Path(path) = expr else {
+        return None;
+    };
+    let written: Vec<String> = path
+        .path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.unraw().to_string())
+        .collect();
+    let resolved = module
+        .iter()
+        .map(|name| name.to_string())
+        .chain(written.iter().cloned())
+        .collect();
+    Some((written, resolved))
+}
+
+/// Adds an operation for the route to the paths.  A route which matches any
+/// method adds every operation the path doesn't already have, and an earlier
+/// route for the same operation takes precedence, as it would in `route!`.
+pub fn insert_route(paths: &mut Paths, route: &ExtractedRoute) {
+    let item = paths.paths.entry(route.path.clone()).or_default();
+    let RefOr::Item(item) = item else {
+        return;
+    };
+    let methods: Vec<&str> = match &route.method {
+        Some(method) => vec![method.as_str()],
+        None => OPERATION_METHODS.to_vec(),
+    };
+    for method in methods {
+        let Some(slot) = operation_mut(item, method) else {
+            continue;
+        };
+        if slot.is_none() {
+            *slot = Some(operation(route));
+        }
+    }
+}
+
+/// The operation slot of a path item for a method, if OpenAPI has one
+pub fn operation_mut<'a>(
+    item: &'a mut PathItem,
+    method: &str,
+) -> Option<&'a mut Option<Operation>> {
+    match method {
+        "GET" => Some(&mut item.get),
+        "PUT" => Some(&mut item.put),
+        "POST" => Some(&mut item.post),
+        "DELETE" => Some(&mut item.delete),
+        "OPTIONS" => Some(&mut item.options),
+        "HEAD" => Some(&mut item.head),
+        "PATCH" => Some(&mut item.patch),
+        "TRACE" => Some(&mut item.trace),
+        _ => None,
+    }
+}
+
+/// The first line of the doc comment becomes the summary, and the whole
+/// comment the description if there's more to it than that
+fn operation(route: &ExtractedRoute) -> Operation {
+    let lines: Vec<&str> = route.docs.iter().map(|line| line.trim()).collect();
+    let text = lines.join("\n").trim().to_string();
+    let summary = lines.iter().find(|line| !line.is_empty());
+    Operation {
+        summary: summary.map(|line| line.to_string()),
+        description: (summary.is_some() && text.contains('\n')).then_some(text),
+        parameters: route.parameters.iter().cloned().map(RefOr::Item).collect(),
+        ..Default::default()
+    }
+}
+
+#[derive(Default)]
+struct RouteVisitor {
+    source: SourceRoutes,
+    /// The inline modules being visited
+    module: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for RouteVisitor {
+    fn visit_item_mod(&mut self, item: &'ast ItemMod) {
+        self.module.push(item.ident.unraw().to_string());
+        syn::visit::visit_item_mod(self, item);
+        self.module.pop();
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let Some(name) = mac.path.segments.last() else {
+            return;
+        };
+        let block = match name.ident.to_string().as_str() {
+            "route" | "route_opt" => {
+                syn::parse2::<MatchStmnt>(mac.tokens.clone()).map(|stmnt| (None, stmnt))
+            }
+            "router" => syn::parse2::<RouterDef>(mac.tokens.clone())
+                .map(|def| (Some(def.sig.ident.unraw().to_string()), def.stmnt)),
+            _ => return,
+        };
+        match block {
+            Ok((router, stmnt)) => {
+                let arms = stmnt
+                    .arms
+                    .into_iter()
+                    .filter_map(|arm| match arm {
+                        MatchArm::Route(route) => Some(route),
+                        _ => None,
+                    })
+                    .collect();
+                self.source.blocks.push(RouteBlock {
+                    router,
+                    module: self.module.clone(),
+                    arms,
+                });
+            }
+            Err(error) => self.source.errors.push(error),
+        }
+    }
+}
+
+/// The method a route matches, or `None` if it matches any method
+fn method_name(method: &Method) -> Option<String> {
+    match method {
+        Method::Named(method) => Some(method.to_string()),
+        Method::Extension(method) => Some(method.value()),
+        Method::Any(_) | Method::Param(_) => None,
+    }
+}
+
+/// Extracts a route reached through the given mounts.  A route which matches
+/// any method takes the method of the mounts, if they match only one, and a
+/// route for another method is unreachable.
+fn extract_route(route: &Route, mount: &Mount) -> Option<ExtractedRoute> {
+    let outer = mount.method.and_then(method_name);
+    let (method, method_template) = match (method_name(&route.method), outer) {
+        (Some(inner), Some(outer)) if inner != outer => return None,
+        (None, Some(outer)) => (Some(outer), mount.method?.template()),
+        (method, _) => (method, route.method.template()),
+    };
+
+    let mut path = String::new();
+    let mut template = String::new();
+    let mut parameters = vec![];
+    let mut wildcards = 0;
+    for component in mount.prefix.iter().copied().chain(&route.path.components) {
+        path.push('/');
+        path.push_str(&segment(component, &mut parameters, &mut wildcards)?);
+        template.push('/');
+        template.push_str(&component.template());
+    }
+    if path.is_empty() {
+        path.push('/');
+        template.push('/');
+    }
+
+    Some(ExtractedRoute {
+        pattern: format!("{} {}", method_template, template),
+        path,
+        method,
+        parameters,
+        docs: route.docs.clone(),
+        line: route.method.span().start().line,
+    })
+}
+
+/// Renders a path segment as an OpenAPI template, adding a parameter for each
+/// value it captures.  Wildcards are named `segment`, `segment2`, and so on.
+/// Returns `None` for `_`, which has no OpenAPI equivalent.
+fn segment(
+    component: &PathComponent,
+    parameters: &mut Vec<Parameter>,
+    wildcards: &mut usize,
+) -> Option<String> {
+    match component {
+        PathComponent::Ident(name) => Some(name.unraw().to_string()),
+        PathComponent::Quoted(segment) => Some(segment.value()),
+        PathComponent::Param(name) => Some(parameter(
+            parameters,
+            name.unraw().to_string(),
+            Schema::new_string(),
+            None,
+        )),
+        PathComponent::Wildcard(_) => {
+            *wildcards += 1;
+            let name = match wildcards {
+                1 => "segment".to_string(),
+                n => format!("segment{}", n),
+            };
+            Some(parameter(
+                parameters,
+                name,
+                Schema::new_string(),
+                Some("Any single path segment"),
+            ))
+        }
+        PathComponent::Rest(_, name) => {
+            let name = name
+                .as_ref()
+                .map(|name| name.unraw().to_string())
+                .unwrap_or_else(|| "rest".to_string());
+            Some(parameter(
+                parameters,
+                name,
+                Schema::new_string(),
+                Some("The rest of the path, which may contain `/`"),
+            ))
+        }
+        PathComponent::Any(_) => None,
+        PathComponent::Extension {
+            stem,
+            extensions,
+            binding,
+        } => {
+            let stem = segment(stem, parameters, wildcards)?;
+            let name = binding
+                .as_ref()
+                .map(|name| name.unraw().to_string())
+                .unwrap_or_else(|| "extension".to_string());
+            let extensions = extensions
+                .iter()
+                .map(|ext| ext.unraw().to_string())
+                .collect();
+            let extension = parameter(
+                parameters,
+                name,
+                Schema::new_str_enum(extensions),
+                Some("The file extension"),
+            );
+            Some(format!("{}.{}", stem, extension))
+        }
+    }
+}
+
+fn parameter(
+    parameters: &mut Vec<Parameter>,
+    name: String,
+    schema: Schema,
+    description: Option<&str>,
+) -> String {
+    let template = format!("{{{}}}", name);
+    let mut parameter = Parameter::path(name, schema);
+    parameter.required = true;
+    parameter.description = description.map(str::to_string);
+    parameters.push(parameter);
+    template
+}
@@ -0,0 +1,128 @@
+pub mod extract_impl;
+
+use std::path::{Path, PathBuf};
+
+use extract_impl::{Extraction, insert_route, parse_routes, resolve_routes};
+use openapiv3::{Info, OpenAPI};
+
+use crate::{Encoding, ExtractArgs};
+
+pub fn exec(args: ExtractArgs) -> Option<String> {
+    let spec = extract_spec(&args);
+
+    let spec_content = match &args.output_format {
+        Some(Encoding::Yaml) | Some(Encoding::Yml) => serde_yaml::to_string(&spec).unwrap(),
+        _ => serde_json::to_string_pretty(&spec).unwrap(),
+    };
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, spec_content).unwrap();
+        return None;
+    }
+
+    Some(spec_content)
+}
+
+/// Builds a spec with a path item for every route found in the sources
+pub fn extract_spec(args: &ExtractArgs) -> OpenAPI {
+    let mut spec = OpenAPI {
+        info: Info {
+            title: args.title.clone(),
+            version: args.api_version.clone(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let extraction = extract_sources(&args.sources, args.verbose);
+    for (file, error) in &extraction.errors {
+        eprintln!("warning: {}: {}", error_location(file, error), error);
+    }
+    for (_, route) in &extraction.routes {
+        insert_route(&mut spec.paths, route);
+    }
+
+    spec
+}
+
+/// The routes defined in the given sources, along with the file each is in.
+/// Mounted routers are resolved across all the sources.  Blocks which can't
+/// be parsed, and mounts which can't be resolved, are returned as errors.
+pub fn extract_sources(sources: &[PathBuf], verbose: bool) -> Extraction<PathBuf> {
+    let mut parsed = vec![];
+    for file in source_files(sources) {
+        if verbose {
+            eprintln!("extracting routes from: {:?}", file);
+        }
+        let source = std::fs::read_to_string(&file).unwrap();
+        let mut routes = parse_routes(&source);
+        routes.module = module_path(&file);
+        parsed.push((file, routes));
+    }
+    resolve_routes(&parsed)
+}
+
+/// The module a source file declares, from its path under `src`, e.g.
+/// `["api", "v1"]` for `src/api/v1.rs` or `src/api/v1/mod.rs`.  Files outside
+/// a `src` directory are taken to be crate roots.
+pub fn module_path(file: &Path) -> Vec<String> {
+    let components: Vec<String> = file
+        .with_extension("")
+        .iter()
+        .map(|component| component.to_string_lossy().into_owned())
+        .collect();
+    let Some(src) = components.iter().rposition(|component| component == "src") else {
+        return vec![];
+    };
+    let mut module = components[src + 1..].to_vec();
+    match module.as_slice() {
+        [.., last] if last == "mod" => {
+            module.pop();
+        }
+        [root] if root == "lib" || root == "main" => module.clear(),
+        _ => {}
+    }
+    module
+}
+
+/// The location of an error in a source file, e.g. `src/main.rs:12:5`
+pub fn error_location(file: &Path, error: &syn::Error) -> String {
+    let start = error.span().start();
+    format!("{}:{}:{}", file.display(), start.line, start.column + 1)
+}
+
+/// The `.rs` files in the given sources, in a stable order.  Directories are
+/// searched recursively, skipping `target` and hidden directories.
+pub fn source_files(sources: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = vec![];
+    for source in sources {
+        if source.is_dir() {
+            collect_source_files(source, &mut files);
+        } else {
+            files.push(source.clone());
+        }
+    }
+    files
+}
+
+fn collect_source_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    entries.sort();
+
+    for entry in entries {
+        let name = entry
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        if entry.is_dir() {
+            if name != "target" && !name.starts_with('.') {
+                collect_source_files(&entry, files);
+            }
+        } else if entry.extension().is_some_and(|ext| ext == "rs") {
+            files.push(entry);
+        }
+    }
+}
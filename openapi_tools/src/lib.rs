@@ -4,8 +4,18 @@ use clap::{Parser, ValueEnum};
 
 pub mod utils;
 
+pub mod route_pattern;
+
 pub mod merge;
 
+pub mod extract;
+
+pub mod check_routes;
+
+pub mod codegen;
+
+pub mod lint;
+
 #[derive(Parser, Debug, Clone)]
 pub struct Args {
     #[command(subcommand)]
@@ -14,6 +24,10 @@ pub struct Args {
 #[derive(clap::Subcommand, Debug, Clone)]
 pub enum Subcommand {
     Merge(MergeArgs),
+    Extract(ExtractArgs),
+    CheckRoutes(CheckRoutesArgs),
+    Codegen(CodegenArgs),
+    Lint(LintArgs),
 }
 
 #[derive(clap::Args, Debug, Clone)]
@@ -59,6 +73,89 @@ pub struct MergeArgs {
     pub use_version_2: bool,
 }
 
+#[derive(clap::Args, Debug, Clone)]
+pub struct ExtractArgs {
+    /// Rust source files, or directories to search for `.rs` files
+    #[arg(required = true)]
+    pub sources: Vec<PathBuf>,
+
+    /// The title of the generated spec
+    #[arg(short, long, default_value = "API")]
+    pub title: String,
+
+    /// The version of the API described by the generated spec
+    #[arg(long = "api-version", default_value = "0.1.0")]
+    pub api_version: String,
+
+    /// Output file path (if not specified, the result will print to stdout)
+    #[arg(short = 'o', long = "output-file")]
+    pub output: Option<PathBuf>,
+
+    /// Output encoding (default is json)
+    #[arg(long = "output-encoding", short = 'E')]
+    pub output_format: Option<Encoding>,
+
+    /// Enable verbose logging output
+    #[arg(long = "verbose", short = 'V', action)]
+    pub verbose: bool,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct CheckRoutesArgs {
+    /// The spec to check the routes against
+    #[arg(short, long)]
+    pub spec: PathBuf,
+
+    /// Rust source files, or directories to search for `.rs` files
+    #[arg(long, required = true)]
+    pub src: Vec<PathBuf>,
+
+    /// Enable verbose logging output
+    #[arg(long = "verbose", short = 'V', action)]
+    pub verbose: bool,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct CodegenArgs {
+    #[command(subcommand)]
+    pub target: CodegenTarget,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum CodegenTarget {
+    /// A handler trait, and a function dispatching requests to it with `route!`
+    RustServer(RustServerArgs),
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct RustServerArgs {
+    /// The spec to generate code from
+    #[arg(short, long)]
+    pub spec: PathBuf,
+
+    /// Output file path (if not specified, the result will print to stdout)
+    #[arg(short = 'o', long = "output-file")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct LintArgs {
+    /// The spec to lint
+    #[arg(short, long)]
+    pub spec: PathBuf,
+
+    /// The rules to check the spec against
+    #[arg(short, long)]
+    pub target: LintTarget,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LintTarget {
+    /// Whether each path can be written as a `route!` pattern
+    #[value(name = "route_match")]
+    RouteMatch,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum Encoding {
     Json,
@@ -0,0 +1,105 @@
+use std::fmt::Display;
+
+use openapiv3::{OpenAPI, Operation, RefOr};
+
+use crate::{
+    LintArgs, LintTarget,
+    route_pattern::{PathConversion, convert_path, path_parameters},
+    utils::load_spec,
+};
+
+/// How a path in the spec can be written as a `route!` pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Classification {
+    /// The pattern is the path, with `{param}` written as `:param`
+    Ok,
+    /// The pattern differs from the path in some other way, such as quoted
+    /// segments or renamed captures
+    Rewritten,
+    /// The path can't be written as a pattern
+    Unsupported,
+}
+
+/// The result of checking a path in the spec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathLint {
+    pub path: String,
+    /// The suggested pattern, or an explanation of why there isn't one
+    pub result: Result<PathConversion, String>,
+}
+
+impl PathLint {
+    pub fn classification(&self) -> Classification {
+        match &self.result {
+            Ok(conversion) if conversion.notes.is_empty() => Classification::Ok,
+            Ok(_) => Classification::Rewritten,
+            Err(_) => Classification::Unsupported,
+        }
+    }
+}
+
+impl Display for Classification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Classification::Ok => f.write_str("ok"),
+            Classification::Rewritten => f.write_str("rewritten"),
+            Classification::Unsupported => f.write_str("unsupported"),
+        }
+    }
+}
+
+impl Display for PathLint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.result {
+            Ok(conversion) => {
+                write!(
+                    f,
+                    "{}: {}: {}",
+                    self.path,
+                    self.classification(),
+                    conversion.pattern
+                )?;
+                for note in &conversion.notes {
+                    write!(f, "\n    {}", note)?;
+                }
+                Ok(())
+            }
+            Err(explanation) => write!(
+                f,
+                "{}: {}\n    {}",
+                self.path,
+                self.classification(),
+                explanation
+            ),
+        }
+    }
+}
+
+pub fn exec(args: LintArgs) -> Vec<PathLint> {
+    let spec = load_spec(&args.spec);
+    match args.target {
+        LintTarget::RouteMatch => lint_route_match(&spec),
+    }
+}
+
+/// Checks whether each path in the spec can be written as a `route!`
+/// pattern, suggesting a pattern for the paths which can
+pub fn lint_route_match(spec: &OpenAPI) -> Vec<PathLint> {
+    let mut lints = vec![];
+    for (path, item) in spec.paths.iter() {
+        let result = match item {
+            RefOr::Item(item) => {
+                let operations: Vec<&Operation> =
+                    item.iter().map(|(_, operation)| operation).collect();
+                path_parameters(spec, item, &operations)
+                    .and_then(|parameters| convert_path(spec, path, &parameters, &[]))
+            }
+            RefOr::Reference { .. } => Err("path item references aren't supported".to_string()),
+        };
+        lints.push(PathLint {
+            path: path.clone(),
+            result,
+        });
+    }
+    lints
+}
@@ -1,5 +1,5 @@
 use clap::Parser;
-use openapi_tools::{Args, Subcommand, merge};
+use openapi_tools::{Args, Subcommand, check_routes, codegen, extract, lint, merge};
 
 fn main() {
     let args = Args::parse();
@@ -10,5 +10,33 @@ fn main() {
                 println!("{}", result);
             }
         }
+        Subcommand::Extract(extract) => {
+            if let Some(result) = extract::exec(extract) {
+                println!("{}", result);
+            }
+        }
+        Subcommand::Codegen(codegen) => {
+            if let Some(result) = codegen::exec(codegen) {
+                println!("{}", result);
+            }
+        }
+        Subcommand::Lint(lint) => {
+            let lints = lint::exec(lint);
+            for lint in &lints {
+                println!("{}", lint);
+            }
+            if lints.iter().any(|lint| lint.result.is_err()) {
+                std::process::exit(1);
+            }
+        }
+        Subcommand::CheckRoutes(check) => {
+            let drift = check_routes::exec(check);
+            for drift in &drift {
+                println!("{}", drift);
+            }
+            if !drift.is_empty() {
+                std::process::exit(1);
+            }
+        }
     }
 }
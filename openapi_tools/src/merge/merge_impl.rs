@@ -12,7 +12,7 @@ pub fn merge_impl(args: &MergeArgs, map: SourceMap) -> OpenAPI {
     let sources = &map.sources;
 
     for (source, overrides) in sources {
-        let spec = get_spec(&args, source, overrides);
+        let spec = get_spec(args, source, overrides);
         result = result.merge(spec).unwrap();
     }
 
@@ -20,12 +20,12 @@ pub fn merge_impl(args: &MergeArgs, map: SourceMap) -> OpenAPI {
 }
 
 pub fn get_spec(args: &MergeArgs, source: &PathBuf, overrides: &Option<PathItem>) -> OpenAPI {
-    let mut spec = get_source_spec(args, &source);
+    let mut spec = get_source_spec(args, source);
 
     if let Some(overrides) = overrides {
         for (_, item) in spec.paths.iter_mut() {
             if let RefOr::Item(item) = item {
-                merge_path_item(item, &overrides);
+                merge_path_item(item, overrides);
             }
         }
     }
@@ -34,7 +34,7 @@ pub fn get_spec(args: &MergeArgs, source: &PathBuf, overrides: &Option<PathItem>
 }
 
 pub fn get_source_spec(args: &MergeArgs, source: &PathBuf) -> OpenAPI {
-    let path = args.relative_path(&source);
+    let path = args.relative_path(source);
 
     if args.verbose {
         eprintln!("getting source spec at path: {:?}", path);
@@ -4,7 +4,7 @@ use std::{collections::BTreeMap, path::PathBuf};
 
 use merge_impl::merge_impl;
 use openapiv3::{
-    ExternalDocumentation, IndexMap, Info, PathItem, Paths, SecurityRequirement, Server, Tag, v2,
+    ExternalDocumentation, IndexMap, Info, PathItem, Paths, SecurityRequirement, Server, Tag,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -102,7 +102,7 @@ impl SourceMap {
             eprintln!("Tamplating complete:\n{}", source);
         }
 
-        let map = match encoding {
+        match encoding {
             Some(Encoding::Json) => serde_json::from_str(&source).unwrap(),
             Some(Encoding::Yaml) => serde_yaml::from_str(&source).unwrap(),
             Some(Encoding::Yml) => serde_yaml::from_str(&source).unwrap(),
@@ -113,8 +113,6 @@ impl SourceMap {
                     serde_yaml::from_str(&source).unwrap()
                 }
             }
-        };
-
-        map
+        }
     }
 }
@@ -0,0 +1,226 @@
+use heck::ToSnakeCase;
+use openapiv3::{OpenAPI, Operation, Parameter, ParameterKind, PathItem, SchemaKind, Type};
+use route_match_syntax::route::Route;
+use syn::{Ident, ext::IdentExt, parse::Parser};
+
+/// A value captured by a `route!` pattern converted from an OpenAPI path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capture {
+    /// The name of the parameter in the spec
+    pub name: String,
+    /// The name the pattern binds the value to.  This is the name used in the
+    /// spec where it can be, so the routes still line up with the spec in
+    /// `openapi_tools check-routes`.
+    pub binding: String,
+    /// Whether the value is the extension matched by a `.{json,csv}` segment
+    pub extension: bool,
+}
+
+/// An OpenAPI path template converted to a `route!` path pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathConversion {
+    /// The path pattern, e.g. `/"user-profiles"/:id`
+    pub pattern: String,
+    pub captures: Vec<Capture>,
+    /// Explanations of where the pattern differs from the template, such as
+    /// segments which had to be quoted
+    pub notes: Vec<String>,
+}
+
+/// Converts a path template to a `route!` path pattern: `{id}` becomes `:id`,
+/// `{id}.{format}` becomes `:id.{json,csv}` if `format` is an enum, and
+/// literal segments which aren't identifiers are quoted.  Captures named in
+/// `reserved` are renamed.
+///
+/// Returns an explanation if the path can't be written as a pattern, e.g.
+/// because it captures part of a segment.
+pub fn convert_path(
+    spec: &OpenAPI,
+    path: &str,
+    parameters: &[&Parameter],
+    reserved: &[&str],
+) -> Result<PathConversion, String> {
+    let mut conversion = PathConversion {
+        pattern: String::new(),
+        captures: vec![],
+        notes: vec![],
+    };
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        let segment = convert_segment(spec, segment, parameters, reserved, &mut conversion)?;
+        conversion.pattern.push('/');
+        conversion.pattern.push_str(&segment);
+    }
+    if conversion.pattern.is_empty() {
+        conversion.pattern.push('/');
+    }
+
+    // Check the result against the macro's own grammar
+    let route = Route::parse_pattern
+        .parse_str(&format!("_ {}", conversion.pattern))
+        .map_err(|error| error.to_string())?;
+    route
+        .validate_captures()
+        .map_err(|error| error.to_string())?;
+    Ok(conversion)
+}
+
+fn convert_segment(
+    spec: &OpenAPI,
+    segment: &str,
+    parameters: &[&Parameter],
+    reserved: &[&str],
+    conversion: &mut PathConversion,
+) -> Result<String, String> {
+    if !segment.contains(['{', '}']) {
+        return Ok(literal(segment, conversion));
+    }
+    if let Some(name) = template_param(segment) {
+        return Ok(format!(":{}", capture(name, false, reserved, conversion)));
+    }
+
+    let Some((stem, extension)) = segment
+        .rsplit_once('.')
+        .and_then(|(stem, ext)| Some((stem, template_param(ext)?)))
+    else {
+        return Err(partial_segment(segment));
+    };
+    let Some(extensions) = parameters
+        .iter()
+        .find(|parameter| parameter.name == extension)
+        .and_then(|parameter| string_enum(spec, parameter))
+    else {
+        return Err(format!(
+            "`{}` captures part of a segment, which `route!` can only match if `{}` is an enum of file extensions",
+            segment, extension
+        ));
+    };
+    if let Some(invalid) = extensions
+        .iter()
+        .find(|value| Ident::parse_any.parse_str(value).is_err())
+    {
+        return Err(format!(
+            "`{}` can only be matched as a file extension if every value of `{}` is an identifier, but `{}` isn't",
+            segment, extension, invalid
+        ));
+    }
+
+    let stem = match template_param(stem) {
+        Some(name) => format!(":{}", capture(name, false, reserved, conversion)),
+        None if !stem.is_empty() && !stem.contains(['{', '}']) => literal(stem, conversion),
+        None => return Err(partial_segment(segment)),
+    };
+    capture(extension, true, reserved, conversion);
+    conversion.notes.push(format!(
+        "`{}` is matched as a file extension, since `{}` is an enum",
+        segment, extension
+    ));
+    Ok(format!("{}.{{{}}}", stem, extensions.join(",")))
+}
+
+fn partial_segment(segment: &str) -> String {
+    format!(
+        "`{}` captures part of a segment, which `route!` can't match; capture the whole segment and parse it in the handler instead",
+        segment
+    )
+}
+
+/// A literal segment, quoted unless it's an identifier
+fn literal(segment: &str, conversion: &mut PathConversion) -> String {
+    if syn::parse_str::<Ident>(segment).is_ok() {
+        return segment.to_string();
+    }
+    let reason = if Ident::parse_any.parse_str(segment).is_ok() {
+        "is a keyword"
+    } else {
+        "isn't an identifier"
+    };
+    conversion
+        .notes
+        .push(format!("`{}` {}, so it's quoted", segment, reason));
+    format!("{:?}", segment)
+}
+
+/// Adds a capture for the parameter, returning the name it's bound to
+fn capture(
+    name: &str,
+    extension: bool,
+    reserved: &[&str],
+    conversion: &mut PathConversion,
+) -> String {
+    let (binding, reason) = if reserved.contains(&name) {
+        (format!("{}_", name), "is reserved")
+    } else if syn::parse_str::<Ident>(name).is_ok() {
+        (name.to_string(), "")
+    } else if Ident::parse_any.parse_str(name).is_ok() {
+        (identifier(name), "is a keyword")
+    } else {
+        (identifier(name), "isn't an identifier")
+    };
+    if !reason.is_empty() {
+        conversion.notes.push(format!(
+            "`{{{}}}` is captured as `{}`, since `{}` {}",
+            name, binding, name, reason
+        ));
+    }
+    conversion.captures.push(Capture {
+        name: name.to_string(),
+        binding: binding.clone(),
+        extension,
+    });
+    binding
+}
+
+/// The name of a parameter which makes up a whole segment, e.g. `id` in `{id}`
+pub fn template_param(segment: &str) -> Option<&str> {
+    let name = segment.strip_prefix('{')?.strip_suffix('}')?;
+    (!name.is_empty() && !name.contains(['{', '}'])).then_some(name)
+}
+
+/// Makes a name usable as an identifier, as a raw identifier if it's a keyword
+pub fn identifier(name: &str) -> String {
+    if syn::parse_str::<Ident>(name).is_ok() {
+        return name.to_string();
+    }
+    let raw = format!("r#{}", name);
+    if syn::parse_str::<Ident>(&raw).is_ok() {
+        return raw;
+    }
+    let name = name.to_snake_case();
+    if syn::parse_str::<Ident>(&name).is_ok() {
+        return name;
+    }
+    format!("_{}", name)
+}
+
+/// The path parameters of the operations, including those declared on their
+/// path item.  The first declaration of a parameter takes precedence.
+pub fn path_parameters<'a>(
+    spec: &'a OpenAPI,
+    item: &'a PathItem,
+    operations: &[&'a Operation],
+) -> Result<Vec<&'a Parameter>, String> {
+    let operation_parameters = operations
+        .iter()
+        .flat_map(|operation| &operation.parameters);
+    let mut parameters: Vec<&Parameter> = vec![];
+    for parameter in operation_parameters.chain(&item.parameters) {
+        let parameter = parameter.resolve(spec).map_err(|error| error.to_string())?;
+        let declared = parameters
+            .iter()
+            .any(|declared| declared.name == parameter.name);
+        if matches!(parameter.kind, ParameterKind::Path { .. }) && !declared {
+            parameters.push(parameter);
+        }
+    }
+    Ok(parameters)
+}
+
+fn string_enum(spec: &OpenAPI, parameter: &Parameter) -> Option<Vec<String>> {
+    let schema = parameter.schema()?.resolve(spec);
+    match &schema.kind {
+        SchemaKind::Type(Type::String(string)) if !string.enumeration.is_empty() => {
+            Some(string.enumeration.clone())
+        }
+        _ => None,
+    }
+}
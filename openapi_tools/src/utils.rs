@@ -1,17 +1,36 @@
-use openapiv3::{IndexMap, OpenAPI, Paths, v2};
-use serde_json;
+use std::path::Path;
 
-pub fn to_v2(spec: OpenAPI) -> v2::OpenAPI {
-    let mut result = v2::OpenAPI::default();
+use openapiv3::{OpenAPI, v2};
+use serde_json;
 
-    result.swagger = "2.0".to_string();
-    result.info = serde_json::from_str(&serde_json::to_string(&spec.info).unwrap()).unwrap();
-    result.paths = serde_json::from_str(&serde_json::to_string(&spec.paths).unwrap()).unwrap();
-    result.security =
-        serde_json::from_str(&serde_json::to_string(&spec.security).unwrap()).unwrap();
-    result.extensions = spec.extensions.clone();
-    result.responses =
-        serde_json::from_str(&serde_json::to_string(&spec.components.responses).unwrap()).unwrap();
+/// Reads a spec as json or yaml, depending on the file extension
+pub fn load_spec(path: &Path) -> OpenAPI {
+    let source = std::fs::read_to_string(path).unwrap();
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&source).unwrap(),
+        Some("yml") => serde_yaml::from_str(&source).unwrap(),
+        Some("yaml") => serde_yaml::from_str(&source).unwrap(),
+        _ => {
+            if let Ok(spec) = serde_json::from_str(&source) {
+                spec
+            } else {
+                serde_yaml::from_str(&source).unwrap()
+            }
+        }
+    }
+}
 
-    result
+pub fn to_v2(spec: OpenAPI) -> v2::OpenAPI {
+    v2::OpenAPI {
+        swagger: "2.0".to_string(),
+        info: serde_json::from_str(&serde_json::to_string(&spec.info).unwrap()).unwrap(),
+        paths: serde_json::from_str(&serde_json::to_string(&spec.paths).unwrap()).unwrap(),
+        security: serde_json::from_str(&serde_json::to_string(&spec.security).unwrap()).unwrap(),
+        extensions: spec.extensions.clone(),
+        responses: serde_json::from_str(
+            &serde_json::to_string(&spec.components.responses).unwrap(),
+        )
+        .unwrap(),
+        ..Default::default()
+    }
 }
@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+use openapi_tools::{
+    CheckRoutesArgs, ExtractArgs,
+    check_routes::{Drift, check, exec},
+    extract::{extract_sources, extract_spec},
+    utils::load_spec,
+};
+
+fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+#[test]
+pub fn test_check_routes() {
+    let spec = load_spec(&fixtures().join("api.spec.yaml"));
+    let routes = extract_sources(&[fixtures()], false).routes;
+    let location = |line: usize| format!("{}:{}", fixtures().join("routes.rs").display(), line);
+
+    let drift = check(&spec, &routes);
+    assert_eq!(
+        drift,
+        [
+            Drift::ParameterMismatch {
+                method: "GET".to_string(),
+                path: "/user/{userId}".to_string(),
+                pattern: "GET /user/:id".to_string(),
+                location: location(11),
+            },
+            Drift::MissingRoute {
+                method: "GET".to_string(),
+                path: "/api/orders/".to_string(),
+            },
+            Drift::MissingRoute {
+                method: "GET".to_string(),
+                path: "/health".to_string(),
+            },
+            Drift::UndocumentedRoute {
+                pattern: "DELETE /user/:id".to_string(),
+                location: location(12),
+            },
+            Drift::UndocumentedRoute {
+                pattern: "POST /a/*/*".to_string(),
+                location: location(16),
+            },
+            Drift::UndocumentedRoute {
+                pattern: "GET /api".to_string(),
+                location: location(31),
+            },
+        ]
+    );
+    assert_eq!(
+        drift[1].to_string(),
+        "GET /api/orders/: no route matches this operation"
+    );
+}
+
+#[test]
+pub fn test_extracted_spec_has_no_drift() {
+    let args = ExtractArgs {
+        sources: vec![fixtures()],
+        title: "Fixture".to_string(),
+        api_version: "1.0.0".to_string(),
+        output: None,
+        output_format: None,
+        verbose: false,
+    };
+    let spec = extract_spec(&args);
+    let routes = extract_sources(&args.sources, false).routes;
+    assert_eq!(check(&spec, &routes), []);
+}
+
+#[test]
+pub fn test_unparsed_blocks_fail_the_check() {
+    let routes = fixtures().join("routes.rs");
+    let drift = exec(CheckRoutesArgs {
+        spec: fixtures().join("api.spec.yaml"),
+        src: vec![routes.clone()],
+        verbose: false,
+    });
+    let location = format!("{}:39:27", routes.display());
+    assert_eq!(
+        drift[0],
+        Drift::Unchecked {
+            location: location.clone(),
+            message: "expected `/`".to_string(),
+        }
+    );
+    assert_eq!(
+        drift[0].to_string(),
+        format!("{}: routes weren't checked: expected `/`", location)
+    );
+    assert_eq!(drift.len(), 7);
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use openapi_tools::{codegen::rust_server, utils::load_spec};
+
+mod petstore {
+    include!("codegen/petstore_server.rs");
+}
+
+use petstore::{ApiHandlers, dispatch};
+
+struct Handlers;
+
+impl ApiHandlers for Handlers {
+    type Request = ();
+    type Response = String;
+
+    fn list_pets(&self, _: &()) -> String {
+        "list".to_string()
+    }
+
+    fn create_pet(&self, _: &()) -> String {
+        "create".to_string()
+    }
+
+    fn my_pets(&self, _: &()) -> String {
+        "mine".to_string()
+    }
+
+    fn show_pet_by_id(&self, _: &(), pet_id: i64) -> String {
+        format!("show {}", pet_id)
+    }
+
+    fn delete_pets_by_pet_id(&self, _: &(), pet_id: i64) -> String {
+        format!("delete {}", pet_id)
+    }
+
+    fn get_photo(&self, _: &(), pet_id: i32, name: &str, format: &str) -> String {
+        format!("photo {} {} {}", pet_id, name, format)
+    }
+
+    fn store_type(&self, _: &(), r#type: &str) -> String {
+        format!("type {}", r#type)
+    }
+
+    fn profiles(&self, _: &()) -> String {
+        "profiles".to_string()
+    }
+
+    fn not_found(&self, _: &()) -> String {
+        "not found".to_string()
+    }
+}
+
+#[test]
+pub fn test_codegen_rust_server() {
+    let spec_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/codegen/petstore.spec.yaml");
+    let code = rust_server::generate(&load_spec(&spec_path));
+    assert_eq!(code, include_str!("codegen/petstore_server.rs"));
+}
+
+#[test]
+pub fn test_generated_dispatch() {
+    let route = |method: &str, path: &str| dispatch(&Handlers, method, path, &());
+
+    assert_eq!(route("GET", "/pets"), "list");
+    assert_eq!(route("POST", "/pets"), "create");
+    assert_eq!(route("GET", "/pets/mine"), "mine");
+    assert_eq!(route("GET", "/pets/42"), "show 42");
+    assert_eq!(route("DELETE", "/pets/42"), "delete 42");
+    assert_eq!(route("GET", "/pets/rex"), "not found");
+    assert_eq!(
+        route("GET", "/pets/7/photos/front.png"),
+        "photo 7 front png"
+    );
+    assert_eq!(route("GET", "/pets/7/photos/front.gif"), "not found");
+    assert_eq!(route("GET", "/store/type/dog"), "type dog");
+    assert_eq!(route("GET", "/user-profiles/2024/static"), "profiles");
+    assert_eq!(route("PUT", "/pets"), "not found");
+}
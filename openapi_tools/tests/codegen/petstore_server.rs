@@ -0,0 +1,84 @@
+// Generated by `openapi_tools codegen rust-server` from Pet Store 1.0.0
+
+/// Handles the operations of Pet Store, with a method for each operation
+pub trait ApiHandlers {
+    /// The request passed on to each handler
+    type Request;
+    /// The response returned by each handler
+    type Response;
+
+    /// List all pets
+    ///
+    /// `GET /pets`
+    fn list_pets(&self, req: &Self::Request) -> Self::Response;
+
+    /// Create a pet
+    ///
+    /// `POST /pets`
+    fn create_pet(&self, req: &Self::Request) -> Self::Response;
+
+    /// `GET /pets/mine`
+    fn my_pets(&self, req: &Self::Request) -> Self::Response;
+
+    /// Info for a specific pet
+    ///
+    /// Info for a specific pet
+    ///
+    /// Returns 404 if there's no pet with the id.
+    ///
+    /// `GET /pets/{petId}`
+    fn show_pet_by_id(&self, req: &Self::Request, pet_id: i64) -> Self::Response;
+
+    /// `DELETE /pets/{petId}`
+    fn delete_pets_by_pet_id(&self, req: &Self::Request, pet_id: i64) -> Self::Response;
+
+    /// `GET /pets/{petId}/photos/{name}.{format}`
+    fn get_photo(&self, req: &Self::Request, pet_id: i32, name: &str, format: &str) -> Self::Response;
+
+    /// `GET /store/type/{type}`
+    fn store_type(&self, req: &Self::Request, r#type: &str) -> Self::Response;
+
+    /// `GET /user-profiles/2024/static`
+    fn profiles(&self, req: &Self::Request) -> Self::Response;
+
+    /// Called for requests which don't match any operation
+    fn not_found(&self, req: &Self::Request) -> Self::Response;
+}
+
+/// Calls the handler for the operation which matches the request, or
+/// `not_found` if there isn't one.  A request doesn't match an operation
+/// if a path parameter can't be parsed to the type in the spec.
+#[allow(non_snake_case)]
+pub fn dispatch<H: ApiHandlers>(
+    handlers: &H,
+    method: &str,
+    path: &str,
+    req: &H::Request,
+) -> H::Response {
+    ::route_match::route! {
+        #![order = specific]
+        match (&method, &path) {
+            /// List all pets
+            GET /pets => handlers.list_pets(req),
+            /// Create a pet
+            POST /pets => handlers.create_pet(req),
+            GET /pets/mine => handlers.my_pets(req),
+            /// Info for a specific pet
+            GET /pets/:petId => try match petId.parse() {
+                Ok(petId) => Some(handlers.show_pet_by_id(req, petId)),
+                Err(_) => None,
+            },
+            DELETE /pets/:petId => try match petId.parse() {
+                Ok(petId) => Some(handlers.delete_pets_by_pet_id(req, petId)),
+                Err(_) => None,
+            },
+            GET /pets/:petId/photos/:name.{jpg,png} => try |format| match petId.parse() {
+                Ok(petId) => Some(handlers.get_photo(req, petId, name, format)),
+                Err(_) => None,
+            },
+            GET /store/"type"/:r#type => handlers.store_type(req, r#type),
+            GET /"user-profiles"/"2024"/"static" => handlers.profiles(req),
+            _ => handlers.not_found(req),
+        }
+    }
+}
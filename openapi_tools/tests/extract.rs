@@ -0,0 +1,232 @@
+use std::path::Path;
+
+use openapi_tools::{
+    Encoding, ExtractArgs,
+    extract::{exec, extract_impl::extract_routes, module_path},
+};
+use openapiv3::{OpenAPI, Operation, Parameter, PathItem, RefOr, SchemaKind, StringType, Type};
+
+fn path_item<'a>(spec: &'a OpenAPI, path: &str) -> &'a PathItem {
+    match spec.paths.get(path) {
+        Some(RefOr::Item(item)) => item,
+        _ => panic!("no path item for {}", path),
+    }
+}
+
+fn parameters(operation: &Operation) -> Vec<&Parameter> {
+    operation
+        .parameters
+        .iter()
+        .map(|parameter| match parameter {
+            RefOr::Item(parameter) => parameter,
+            _ => panic!("unexpected reference"),
+        })
+        .collect()
+}
+
+#[test]
+pub fn test_extract() {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let args = ExtractArgs {
+        sources: vec![Path::new(manifest_dir).join("tests/fixtures")],
+        title: "Fixture".to_string(),
+        api_version: "1.0.0".to_string(),
+        output: None,
+        output_format: Some(Encoding::Yaml),
+        verbose: false,
+    };
+
+    let result = exec(args).unwrap();
+    println!("result:\n{}", result);
+    let spec: OpenAPI = serde_yaml::from_str(&result).unwrap();
+    assert_eq!(spec.info.title, "Fixture");
+
+    let paths: Vec<&str> = spec.paths.keys().map(String::as_str).collect();
+    assert_eq!(
+        paths,
+        [
+            "/user/me",
+            "/user/{id}",
+            "/assets/{path}",
+            "/report/{id}.{format}",
+            "/a/{segment}/{segment2}",
+            "/dav/{rest}",
+            "/api/orders",
+            "/api",
+        ]
+    );
+
+    let user = path_item(&spec, "/user/{id}");
+    let get = user.get.as_ref().unwrap();
+    assert_eq!(get.summary.as_deref(), Some("Fetch a user"));
+    assert_eq!(
+        get.description.as_deref(),
+        Some("Fetch a user\n\nReturns 404 if there's no user with the id.")
+    );
+    let params = parameters(get);
+    assert_eq!(params.len(), 1);
+    assert_eq!(params[0].name, "id");
+    assert!(params[0].required);
+    assert!(user.delete.as_ref().unwrap().summary.is_none());
+    assert!(user.post.is_none());
+
+    // `_` matches every method, so every operation is generated
+    let assets = path_item(&spec, "/assets/{path}");
+    assert_eq!(assets.iter().count(), 8);
+    let head = assets.head.as_ref().unwrap();
+    assert_eq!(head.summary.as_deref(), Some("Serve a static file"));
+    assert_eq!(
+        parameters(head)[0].description.as_deref(),
+        Some("The rest of the path, which may contain `/`")
+    );
+
+    let report = path_item(&spec, "/report/{id}.{format}");
+    let params = parameters(report.get.as_ref().unwrap());
+    assert_eq!(params[1].name, "format");
+    let Some(RefOr::Item(schema)) = params[1].schema() else {
+        panic!("expected an inline schema");
+    };
+    let SchemaKind::Type(Type::String(StringType { enumeration, .. })) = &schema.kind else {
+        panic!("expected a string schema");
+    };
+    assert_eq!(enumeration, &["json", "csv"]);
+
+    // OpenAPI has no operation for extension methods
+    assert_eq!(path_item(&spec, "/dav/{rest}").iter().count(), 0);
+
+    // The first arm for an operation wins
+    let orders = path_item(&spec, "/api/orders");
+    assert_eq!(
+        orders.post.as_ref().unwrap().summary.as_deref(),
+        Some("Create an order")
+    );
+}
+
+#[test]
+pub fn test_extract_errors() {
+    let source = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/routes.rs"
+    ))
+    .unwrap();
+    let (routes, errors) = extract_routes(&source);
+    assert_eq!(routes.len(), 10);
+    assert_eq!(routes[1].line, 11);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span().start().line, 39);
+
+    let (routes, errors) = extract_routes("fn main() {");
+    assert!(routes.is_empty());
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+pub fn test_extract_mounted_routers() {
+    let source = r#"
+        fn handle(req: &Request) -> Response {
+            route! {
+                match req {
+                    GET /v1/ * /.. => mount v1::routes,
+                    _ /files/.. => mount files,
+                    _ => not_found(),
+                }
+            }
+        }
+
+        router! {
+            fn routes(method: &str, path: &str) -> Option<Response> {
+                match (method, path) {
+                    _ /user/:id => Some(user(id)),
+                    POST /user => None,
+                    _ /a/.. => mount nested,
+                }
+            }
+        }
+
+        router! {
+            fn nested(method: &str, path: &str) -> Option<Response> {
+                match (method, path) {
+                    GET /b/ * => Some(b()),
+                }
+            }
+        }
+    "#;
+    let (routes, errors) = extract_routes(source);
+
+    // Only the `GET` arms are reachable through the `GET` mount
+    let patterns: Vec<&str> = routes.iter().map(|route| route.pattern.as_str()).collect();
+    assert_eq!(patterns, ["GET /v1/*/user/:id", "GET /v1/*/a/b/*"]);
+    let paths: Vec<&str> = routes.iter().map(|route| route.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        ["/v1/{segment}/user/{id}", "/v1/{segment}/a/b/{segment2}"]
+    );
+    assert_eq!(routes[0].method.as_deref(), Some("GET"));
+    assert_eq!(routes[0].line, 15);
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span().start().line, 6);
+    assert_eq!(
+        errors[0].to_string(),
+        "the mounted routes aren't extracted, since there's no `router!` with this name in the sources"
+    );
+}
+
+#[test]
+pub fn test_extract_routers_by_module() {
+    let source = r#"
+        fn handle(req: &Request) -> Response {
+            route! {
+                match req {
+                    _ /v1/.. => mount api_v1::route,
+                    _ /v2/.. => mount api_v2::route,
+                    _ /any/.. => mount route,
+                    _ => not_found(),
+                }
+            }
+        }
+
+        mod api_v1 {
+            router! {
+                pub fn route(method: &str, path: &str) -> Option<Response> {
+                    match (method, path) {
+                        GET /user => Some(user()),
+                    }
+                }
+            }
+        }
+
+        mod api_v2 {
+            router! {
+                pub fn route(method: &str, path: &str) -> Option<Response> {
+                    match (method, path) {
+                        GET /users => Some(users()),
+                    }
+                }
+            }
+        }
+    "#;
+    let (routes, errors) = extract_routes(source);
+
+    let patterns: Vec<&str> = routes.iter().map(|route| route.pattern.as_str()).collect();
+    assert_eq!(patterns, ["GET /v1/user", "GET /v2/users"]);
+
+    // Neither router is at `route` in the root module
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span().start().line, 7);
+    assert_eq!(
+        errors[0].to_string(),
+        "the mounted routes aren't extracted, since more than one `router!` in the sources could be mounted here: `api_v1::route`, `api_v2::route`"
+    );
+}
+
+#[test]
+pub fn test_module_path() {
+    assert_eq!(module_path(Path::new("src/api/v1.rs")), ["api", "v1"]);
+    assert_eq!(module_path(Path::new("crate/src/api/mod.rs")), ["api"]);
+    assert_eq!(module_path(Path::new("src/main.rs")), Vec::<String>::new());
+    assert_eq!(
+        module_path(Path::new("tests/fixtures/routes.rs")),
+        Vec::<String>::new()
+    );
+}
@@ -0,0 +1,43 @@
+use route_match::{route, router};
+
+fn handle(req: &Request) -> Response {
+    route! {
+        match req {
+            /// The current user
+            GET /user/me => current_user(),
+            /// Fetch a user
+            ///
+            /// Returns 404 if there's no user with the id.
+            GET /user/:id => get_user(id),
+            DELETE /user/:id => delete_user(id),
+            /// Serve a static file
+            _ /assets/..:path => asset(path),
+            GET /report/:id.{json,csv} => |format| report(id, format),
+            POST /a/ * / * => wildcards(),
+            "PROPFIND" /dav/.. => propfind(),
+            _ /api/.. => mount api,
+            _ => not_found(),
+        }
+    }
+}
+
+router! {
+    pub fn api(method: &str, path: &str) -> Option<Response> {
+        match (method, path) {
+            /// Create an order
+            POST /orders => Some(create_order()),
+            /// This arm is shadowed by the one above
+            POST /orders => None,
+            GET / => Some(index()),
+        }
+    }
+}
+
+fn broken(req: &Request) -> Response {
+    route_match::route! {
+        match req {
+            GET /user/:id + => get_user(id),
+            _ => not_found(),
+        }
+    }
+}
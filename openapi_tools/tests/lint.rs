@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use openapi_tools::{
+    lint::{Classification, lint_route_match},
+    utils::load_spec,
+};
+
+#[test]
+pub fn test_lint_route_match() {
+    let spec =
+        load_spec(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/lint.spec.yaml"));
+    let lints = lint_route_match(&spec);
+
+    let classifications: Vec<(&str, Classification)> = lints
+        .iter()
+        .map(|lint| (lint.path.as_str(), lint.classification()))
+        .collect();
+    assert_eq!(
+        classifications,
+        [
+            ("/users/{id}", Classification::Ok),
+            ("/user-profiles/{type}", Classification::Rewritten),
+            ("/reports/{name}.{format}", Classification::Rewritten),
+            ("/exports/{name}.{format}", Classification::Unsupported),
+            ("/api/v{version}/status", Classification::Unsupported),
+        ]
+    );
+
+    let output: Vec<String> = lints.iter().map(|lint| lint.to_string()).collect();
+    assert_eq!(output[0], "/users/{id}: ok: /users/:id");
+    assert_eq!(
+        output[1],
+        "/user-profiles/{type}: rewritten: /\"user-profiles\"/:r#type\n    \
+         `user-profiles` isn't an identifier, so it's quoted\n    \
+         `{type}` is captured as `r#type`, since `type` is a keyword"
+    );
+    assert_eq!(
+        output[2],
+        "/reports/{name}.{format}: rewritten: /reports/:name.{json,csv}\n    \
+         `{name}.{format}` is matched as a file extension, since `format` is an enum"
+    );
+    assert!(output[3].starts_with("/exports/{name}.{format}: unsupported\n"));
+    assert!(output[3].contains("`format` is an enum of file extensions"));
+    assert!(output[4].starts_with("/api/v{version}/status: unsupported\n"));
+    assert!(output[4].contains("`v{version}` captures part of a segment"));
+}
@@ -13,7 +13,7 @@ pub fn test_merge() {
     let file_path = Path::new(manifest_dir).join("tests/openapi.template.yaml");
 
     let args = MergeArgs {
-        file: Some(file_path.into()),
+        file: Some(file_path),
         json: None,
         yaml: None,
         encoding: None,
@@ -23,7 +23,7 @@ pub fn test_merge() {
         }],
         output: None,
         output_format: Some(Encoding::Yaml),
-        working_directory: Some(working_dir.into()),
+        working_directory: Some(working_dir),
         verbose: false,
         use_version_2: false,
     };
@@ -45,7 +45,7 @@ pub fn test_merge_to_v2() {
     let file_path = Path::new(manifest_dir).join("tests/openapi.template.yaml");
 
     let args = MergeArgs {
-        file: Some(file_path.into()),
+        file: Some(file_path),
         json: None,
         yaml: None,
         encoding: None,
@@ -55,7 +55,7 @@ pub fn test_merge_to_v2() {
         }],
         output: None,
         output_format: Some(Encoding::Yaml),
-        working_directory: Some(working_dir.into()),
+        working_directory: Some(working_dir),
         verbose: false,
         use_version_2: true,
     };
@@ -0,0 +1,84 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use quote::quote_spanned;
+use route_match_syntax::match_stmnt::MatchArm;
+use syn::spanned::Spanned;
+
+use crate::internal::internals;
+use crate::internal::Internals;
+
+// Everything in this module expands to nothing unless the `coverage` feature
+// is enabled, in which case the generated code refers to the counters in
+// `route_match::coverage`.
+
+/// Declares a hit counter for each arm of the block, and registers them in
+/// `route_match::coverage::BLOCKS` when the program is linked
+pub fn block(arms: &[MatchArm]) -> TokenStream {
+    if !cfg!(feature = "coverage") {
+        return quote! {};
+    }
+
+    let Internals { _coverage, .. } = internals();
+    let counters: Vec<TokenStream> = arms.iter().map(counter).collect();
+    let count = counters.len();
+
+    // `line!()` is spanned at the arm, so clippy mistakes the line number for
+    // the source it points to when that's a literal like `0`
+    quote! {
+        #[allow(clippy::zero_prefixed_literal)]
+        static #_coverage: ::route_match::coverage::Block<#count> =
+            ::route_match::coverage::Block::new([#(#counters),*]);
+        const _: () = {
+            #[::route_match::__private::linkme::distributed_slice(::route_match::coverage::BLOCKS)]
+            #[linkme(crate = ::route_match::__private::linkme)]
+            static BLOCK: &[::route_match::coverage::Arm] = #_coverage.arms();
+        };
+    }
+}
+
+/// Counts a request taking the arm at `index`
+pub fn hit(index: usize) -> TokenStream {
+    if !cfg!(feature = "coverage") {
+        return quote! {};
+    }
+
+    let Internals { _coverage, .. } = internals();
+    quote! {
+        #_coverage.hit(#index);
+    }
+}
+
+/// The counter for an arm, located at its pattern with `file!()` and `line!()`
+fn counter(arm: &MatchArm) -> TokenStream {
+    match arm {
+        MatchArm::Route(route) => {
+            let method = route.method.template();
+            let host = match &route.host {
+                Some(host) => {
+                    let host = host.template();
+                    quote! { Some(#host) }
+                }
+                None => quote! { None },
+            };
+            let path = route.path.template();
+            quote_spanned! { route.span() =>
+                ::route_match::coverage::Arm::new(#method, #host, #path, file!(), line!())
+            }
+        }
+        MatchArm::Pattern(arm) => {
+            let pattern = &arm.pattern;
+            quote_spanned! { pattern.span() =>
+                ::route_match::coverage::Arm::new(
+                    ::route_match::RoutePattern::method(&#pattern),
+                    None,
+                    ::route_match::RoutePattern::path(&#pattern),
+                    file!(),
+                    line!(),
+                )
+            }
+        }
+        MatchArm::Default(expr) => quote_spanned! { expr.span() =>
+            ::route_match::coverage::Arm::default_arm(file!(), line!())
+        },
+    }
+}
@@ -0,0 +1,121 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use quote::quote_spanned;
+use route_match_syntax::match_stmnt::MatchArm;
+use route_match_syntax::match_stmnt::MatchStmnt;
+use route_match_syntax::route::ArmKind;
+use route_match_syntax::route::Route;
+use syn::spanned::Spanned;
+
+use crate::internal::internals;
+use crate::internal::Internals;
+use crate::match_stmnt::allow_lints;
+use crate::match_stmnt::MatchArgCodegen;
+use crate::method;
+use crate::route::generate_mount;
+use crate::route::RouteCodegen;
+
+/// Generates a `route_match::Explanation` of how the request fares against
+/// each arm of the block, in the order they're tried.  Arm expressions aren't
+/// evaluated, except for `try` and `mount` arms whose patterns match, since
+/// whether they match depends on their result.
+pub fn generate(stmnt: &MatchStmnt) -> TokenStream {
+    let Internals { _explained, .. } = internals();
+    let routes = stmnt.arms.iter().filter_map(|arm| match arm {
+        MatchArm::Route(route) => Some(route),
+        _ => None,
+    });
+    let uses_host = routes.clone().any(|route| route.host.is_some());
+    let uses_headers = routes.clone().any(|route| !route.headers.is_empty());
+    let method_and_path = stmnt.arg.generate(uses_host, uses_headers);
+    let arms: Vec<TokenStream> = stmnt.arms.iter().map(explain_arm).collect();
+    let allow = allow_lints();
+
+    quote! {
+        {
+            #method_and_path
+            #allow
+            let #_explained = ::std::vec![#(#arms),*];
+            ::route_match::Explanation::new(#_explained)
+        }
+    }
+}
+
+fn explain_arm(arm: &MatchArm) -> TokenStream {
+    match arm {
+        MatchArm::Route(route) => explain_route(route),
+        MatchArm::Pattern(arm) => {
+            let Internals { _path_str, .. } = internals();
+            let pattern = &arm.pattern;
+            let method = method::request_method_str();
+            quote_spanned! { pattern.span() =>
+                ::route_match::ArmExplanation::new(
+                    ::std::string::ToString::to_string(&#pattern),
+                    line!(),
+                    if ::route_match::RoutePattern::matches(&#pattern, #method, #_path_str) {
+                        Ok(())
+                    } else {
+                        Err(::route_match::Mismatch::Pattern)
+                    },
+                )
+            }
+        }
+        MatchArm::Default(expr) => quote_spanned! { expr.span() =>
+            ::route_match::ArmExplanation::new(::std::string::ToString::to_string("_"), line!(), Ok(()))
+        },
+    }
+}
+
+/// Reports the first check the request fails, or evaluates a delegating arm
+/// to see whether it declines the request
+fn explain_route(route: &Route) -> TokenStream {
+    let pattern = match &route.host {
+        Some(host) => format!(
+            "{} {}{}",
+            route.method.template(),
+            host.template(),
+            route.path.template()
+        ),
+        None => format!("{} {}", route.method.template(), route.path.template()),
+    };
+    let (conditions, mismatches): (Vec<TokenStream>, Vec<TokenStream>) = route
+        .rejections()
+        .into_iter()
+        .map(|rejection| (rejection.condition, rejection.mismatch))
+        .unzip();
+    let matched = match route.kind {
+        ArmKind::Expr => quote! { Ok(()) },
+        ArmKind::Try => {
+            let arg_assignments = route.arg_assignments();
+            let expr = &route.expr;
+            quote_spanned! { expr.span() =>
+                #arg_assignments
+                match #expr {
+                    Some(_) => Ok(()),
+                    None => Err(::route_match::Mismatch::TryDeclined),
+                }
+            }
+        }
+        ArmKind::Mount => {
+            let mount = generate_mount(route);
+            quote! {
+                match { #mount } {
+                    Some(_) => Ok(()),
+                    None => Err(::route_match::Mismatch::MountDeclined),
+                }
+            }
+        }
+    };
+
+    quote_spanned! { route.span() =>
+        ::route_match::ArmExplanation::new(
+            ::std::string::ToString::to_string(#pattern),
+            line!(),
+            #(if #conditions {
+                Err(#mismatches)
+            } else)* {
+                #matched
+            },
+        )
+    }
+}
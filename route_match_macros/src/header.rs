@@ -0,0 +1,32 @@
+use quote::quote_spanned;
+use route_match_syntax::header::HeaderPredicate;
+use syn::LitStr;
+
+use crate::internal::internals;
+use crate::internal::Internals;
+use crate::route::Rejection;
+
+pub trait HeaderCodegen {
+    /// Rejects requests whose header doesn't match the predicate
+    fn rejection(&self) -> Rejection;
+}
+
+impl HeaderCodegen for HeaderPredicate {
+    fn rejection(&self) -> Rejection {
+        let Internals { _headers, .. } = internals();
+        let name = LitStr::new(&self.name, self.span);
+        let value = LitStr::new(&self.value, self.span);
+        Rejection {
+            condition: quote_spanned! { self.span =>
+                !::route_match::headers::matches(#name, #_headers.header_str(#name), #value)
+            },
+            mismatch: quote_spanned! { self.span =>
+                ::route_match::Mismatch::Header {
+                    name: #name,
+                    expected: #value,
+                    found: #_headers.header_str(#name).map(::std::string::ToString::to_string),
+                }
+            },
+        }
+    }
+}
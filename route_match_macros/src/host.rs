@@ -0,0 +1,71 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use quote::quote_spanned;
+use route_match_syntax::host::Host;
+use route_match_syntax::host::HostLabel;
+use syn::LitStr;
+
+use crate::internal::internals;
+use crate::internal::Internals;
+use crate::route::Rejection;
+
+pub trait HostCodegen {
+    fn rejections(&self) -> Vec<Rejection>;
+    fn arg_assignments(&self) -> TokenStream;
+}
+
+impl HostCodegen for Host {
+    /// Hosts are matched label by label, ignoring case
+    fn rejections(&self) -> Vec<Rejection> {
+        let Internals { _host, .. } = internals();
+        let count = self.labels.len();
+        let mut rejections = vec![Rejection {
+            condition: quote_spanned! { self.span() =>
+                #_host.len() != #count
+            },
+            mismatch: quote_spanned! { self.span() =>
+                ::route_match::Mismatch::HostLength {
+                    expected: #count,
+                    found: #_host.len(),
+                }
+            },
+        }];
+        for (i, label) in self.labels.iter().enumerate() {
+            if let HostLabel::Name(name, span) = label {
+                let name = LitStr::new(name, *span);
+                rejections.push(Rejection {
+                    condition: quote_spanned! { *span =>
+                        !#_host[#i].eq_ignore_ascii_case(#name)
+                    },
+                    mismatch: quote_spanned! { *span =>
+                        ::route_match::Mismatch::HostLabel {
+                            index: #i,
+                            expected: #name,
+                            found: ::std::string::ToString::to_string(#_host[#i]),
+                        }
+                    },
+                });
+            }
+        }
+        rejections
+    }
+
+    fn arg_assignments(&self) -> TokenStream {
+        let Internals { _host, .. } = internals();
+        let assignments: Vec<TokenStream> = self
+            .labels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, label)| match label {
+                HostLabel::Param(name) => Some(quote_spanned! { name.span() =>
+                    let #name = #_host[#i];
+                }),
+                _ => None,
+            })
+            .collect();
+
+        quote! {
+            #(#assignments)*
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use proc_macro2::Span;
+use syn::Ident;
+
+/// The variables declared by generated code.  They're created with a
+/// mixed-site span, so they resolve at the macro's definition site: arm
+/// expressions can neither read nor shadow them, whatever they're called.
+pub struct Internals {
+    pub _method: Ident,
+    pub _path_str: Ident,
+    pub _path: Ident,
+    pub _host_str: Ident,
+    pub _host: Ident,
+    pub _headers: Ident,
+    pub _request: Ident,
+    pub _span: Ident,
+    pub _coverage: Ident,
+    pub _enter: Ident,
+    pub _delegated: Ident,
+    pub _prefix: Ident,
+    pub _routed: Ident,
+    pub _matched: Ident,
+    pub _explained: Ident,
+    pub _stem: Ident,
+    pub byte_offset: Ident,
+    pub segment_count: Ident,
+    pub in_segment: Ident,
+}
+
+pub fn internals() -> Internals {
+    let ident = |name: &str| Ident::new(name, Span::mixed_site());
+    Internals {
+        _method: ident("_method"),
+        _path_str: ident("_path_str"),
+        _path: ident("_path"),
+        _host_str: ident("_host_str"),
+        _host: ident("_host"),
+        _headers: ident("_headers"),
+        _request: ident("_request"),
+        _span: ident("_span"),
+        _coverage: ident("_COVERAGE"),
+        _enter: ident("_enter"),
+        _delegated: ident("_delegated"),
+        _prefix: ident("_prefix"),
+        _routed: ident("_routed"),
+        _matched: ident("_matched"),
+        _explained: ident("_explained"),
+        _stem: ident("_stem"),
+        byte_offset: ident("byte_offset"),
+        segment_count: ident("segment_count"),
+        in_segment: ident("in_segment"),
+    }
+}
@@ -1,12 +1,45 @@
 use proc_macro::TokenStream;
+mod coverage;
+mod explain;
+mod header;
+mod host;
+mod internal;
 mod match_stmnt;
 mod method;
-mod path;
+mod order;
+mod pattern;
 mod route;
 mod route_impl;
+mod router;
+mod testing;
+mod trace;
 
-///
 #[proc_macro]
 pub fn route(input: TokenStream) -> TokenStream {
     route_impl::parse(input.into()).into()
 }
+
+#[proc_macro]
+pub fn route_opt(input: TokenStream) -> TokenStream {
+    route_impl::parse_optional(input.into()).into()
+}
+
+#[proc_macro]
+pub fn route_explain(input: TokenStream) -> TokenStream {
+    route_impl::parse_explain(input.into()).into()
+}
+
+#[proc_macro]
+pub fn route_matches(input: TokenStream) -> TokenStream {
+    route_impl::parse_matches(input.into()).into()
+}
+
+#[proc_macro]
+pub fn route_pattern(input: TokenStream) -> TokenStream {
+    route_impl::parse_pattern(input.into()).into()
+}
+
+#[proc_macro]
+pub fn router(input: TokenStream) -> TokenStream {
+    route_impl::parse_router(input.into()).into()
+}
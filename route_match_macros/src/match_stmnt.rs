@@ -1,175 +1,278 @@
+use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use quote::quote;
 use quote::quote_spanned;
-use syn::braced;
-use syn::parenthesized;
-use syn::parse::Parse;
+use route_match_syntax::match_stmnt::MatchArg;
+use route_match_syntax::match_stmnt::MatchArm;
+use route_match_syntax::match_stmnt::MatchStmnt;
+use route_match_syntax::options::Order;
+use syn::parse_quote_spanned;
 use syn::spanned::Spanned;
-use syn::token::Paren;
 use syn::Expr;
-use syn::Token;
+use syn::Ident;
 
-use crate::route::Route;
+use crate::coverage;
+use crate::internal::internals;
+use crate::internal::Internals;
+use crate::order;
+use crate::pattern::PatternArmCodegen;
+use crate::route::RouteCodegen;
+use crate::testing;
+use crate::trace;
 
-#[derive(Debug)]
-pub struct MatchStmnt {
-    arg: MatchArg,
-    arms: Vec<MatchArm>,
+pub trait MatchArgCodegen {
+    fn generate(&self, uses_host: bool, uses_headers: bool) -> TokenStream;
 }
 
-#[derive(Debug)]
-pub enum MatchArg {
-    RequestProvider(Expr),
-    ComponentProviders {
-        method_provider: Expr,
-        path_provider: Expr,
-    },
+pub trait MatchStmntCodegen {
+    fn order_arms(&mut self) -> syn::Result<Vec<usize>>;
+    fn generate(&self, optional: bool) -> TokenStream;
+    fn generate_router(&self, optional: bool, table: Option<&TokenStream>) -> TokenStream;
 }
 
-impl Parse for MatchArg {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        if input.peek(Paren) {
-            // If we're parsing a parenthetical arg,
-            // we parse the match arg within the parens
-            let content;
-            parenthesized!(content in input);
-            return content.parse();
-        }
-        let first: Expr = input.parse()?;
-        if input.peek(Token![,]) {
-            let _: Token![,] = input.parse()?;
-            let second: Expr = input.parse()?;
-            return Ok(Self::ComponentProviders {
-                method_provider: first,
-                path_provider: second,
-            });
-        }
-        Ok(Self::RequestProvider(first))
-    }
+pub trait MatchArmCodegen {
+    fn generate_conditional(
+        &self,
+        optional: bool,
+        index: usize,
+        routes: Option<&TokenStream>,
+    ) -> TokenStream;
 }
 
-#[derive(Debug)]
-pub enum MatchArm {
-    Route(Route),
-    Default(Expr),
-}
-
-impl Parse for MatchArm {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        if input.peek(Token![_]) {
-            let forked_input = input.fork();
-            let _: Token![_] = forked_input.parse()?;
-            if forked_input.peek(Token![=>]) {
-                // parse Default arm
-                let _: Token![_] = input.parse()?;
-                let _: Token![=>] = input.parse()?;
-                let expr: Expr = input.parse()?;
-                if input.peek(Token![,]) {
-                    let _: Token![,] = input.parse()?;
+impl MatchArgCodegen for MatchArg {
+    /// Declares the request components the arms are matched against.  The
+    /// host and headers are only read from a request provider if an arm uses them.
+    fn generate(&self, uses_host: bool, uses_headers: bool) -> TokenStream {
+        let Internals { _headers, .. } = internals();
+        let tokens = match self {
+            MatchArg::RequestProvider(req) => generate_request_args(req, uses_host, uses_headers),
+            MatchArg::ComponentProviders {
+                method_provider: method,
+                path_provider: components,
+                host_provider: host,
+                headers_provider: headers,
+            } => {
+                let component_args = generate_component_args(method, components);
+                let host_arg = host
+                    .as_deref()
+                    .map(|host| generate_host_arg(quote! { #host }, host.span()));
+                let headers_arg = headers.as_deref().map(|headers| {
+                    quote_spanned! { headers.span() =>
+                        use ::route_match::HeaderProvider as _;
+                        let #_headers = &#headers;
+                    }
+                });
+                quote! {
+                    #component_args
+                    #host_arg
+                    #headers_arg
                 }
-                return Ok(Self::Default(expr));
             }
+        };
+
+        quote! {
+            #tokens
         }
-        let route: Route = input.parse()?;
-        if input.peek(Token![,]) {
-            let _: Token![,] = input.parse()?;
-        }
-        Ok(Self::Route(route))
     }
 }
 
-impl Parse for MatchStmnt {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let _: Token![match] = input.parse()?;
-        let arg: MatchArg = input.parse()?;
-        let content;
-        let mut arms: Vec<MatchArm> = vec![];
-        braced!(content in input);
-        while !content.is_empty() {
-            arms.push(content.parse()?);
+/// Reads the request components through the provider traits re-exported by `route_match`
+fn generate_request_args(
+    request_provider: &Expr,
+    uses_host: bool,
+    uses_headers: bool,
+) -> TokenStream {
+    let Internals {
+        _headers, _request, ..
+    } = internals();
+    let span = request_provider.span();
+    let component_args = generate_component_args(
+        &parse_quote_spanned! { span => &#_request.method_str() },
+        &parse_quote_spanned! { span => &#_request.path_str() },
+    );
+    let host_arg = uses_host.then(|| {
+        let host_arg = generate_host_arg(
+            quote_spanned! { span => #_request.host_str().unwrap_or_default() },
+            span,
+        );
+        quote_spanned! { span =>
+            use ::route_match::HostProvider as _;
+            #host_arg
         }
-
-        Ok(Self { arg, arms })
+    });
+    let headers_arg = uses_headers.then(|| {
+        quote_spanned! { span =>
+            use ::route_match::HeaderProvider as _;
+            let #_headers = #_request;
+        }
+    });
+    quote_spanned! { span =>
+        use ::route_match::HttpMethodProvider as _;
+        use ::route_match::UrlPathProvider as _;
+        let #_request = &#request_provider;
+        #component_args
+        #host_arg
+        #headers_arg
     }
 }
 
-impl MatchArg {
-    pub fn generate(&self) -> TokenStream {
-        let tokens = match self {
-            MatchArg::RequestProvider(req) => Self::generate_request_args(req),
-            MatchArg::ComponentProviders {
-                method_provider: method,
-                path_provider: components,
-            } => Self::generate_component_args(method, components),
-        };
-
-        quote! {
-            #tokens
-        }
+pub fn generate_component_args(method_provider: &Expr, path_provider: &Expr) -> TokenStream {
+    let Internals {
+        _method,
+        _path_str,
+        _path,
+        ..
+    } = internals();
+    let span = method_provider.span();
+    let method_decl = quote_spanned! { span =>
+        let #_method = #method_provider;
+    };
+    let span = path_provider.span();
+    let path_decl = quote_spanned! { span =>
+        let #_path_str = #path_provider;
+        let #_path: Vec<&str> = #_path_str
+            .split('/')
+            .filter(|comp| !comp.is_empty())
+            .collect();
+    };
+    quote! {
+        #method_decl
+        #path_decl
     }
+}
 
-    pub fn generate_request_args(_request_provider: &Expr) -> TokenStream {
-        todo!()
-        // let span = request_provider.span();
-        // quote_spanned! { span =>
-        //     let _method: &str = HttpMethodProvider::method_str(#request_provider);
-        //     let _path_str: &str = UrlPathProvider::path_str(#request_provider);
-        //     let _path: Vec<&str> = _path_str
-        //         .clone()
-        //         .split('/')
-        //         .filter(|comp| !comp.is_empty())
-        //         .collect();
-        // }
+/// The host is split into labels, without the port or a trailing `.`
+pub fn generate_host_arg(host_provider: TokenStream, span: Span) -> TokenStream {
+    let Internals {
+        _host_str, _host, ..
+    } = internals();
+    quote_spanned! { span =>
+        let #_host_str = #host_provider;
+        let #_host: Vec<&str> = #_host_str
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('.')
+            .split('.')
+            .collect();
     }
+}
 
-    pub fn generate_component_args(method_provider: &Expr, path_provider: &Expr) -> TokenStream {
-        let span = method_provider.span();
-        let method_decl = quote_spanned! { span =>
-            let _method = #method_provider;
-        };
-        let span = path_provider.span();
-        let path_decl = quote_spanned! { span =>
-            let _path_str = #path_provider;
-            let _path: Vec<&str> = _path_str
-                .split('/')
-                .filter(|comp| !comp.is_empty())
-                .collect();
-        };
-        quote! {
-            #method_decl
-            #path_decl
+impl MatchStmntCodegen for MatchStmnt {
+    /// Reorders the arms as set by `#![order = ...]`, returning the position
+    /// each arm was written at
+    fn order_arms(&mut self) -> syn::Result<Vec<usize>> {
+        match self.options.order {
+            Order::First => Ok((0..self.arms.len()).collect()),
+            Order::Specific => order::sort_by_specificity(&mut self.arms),
         }
     }
-}
 
-impl MatchStmnt {
-    pub fn generate(&self) -> TokenStream {
-        let method_and_path = &self.arg.generate();
+    /// Generates the routing expression.  When `optional` is set, the result of
+    /// a matching arm is wrapped in `Some`, and `None` is returned if no arm matches.
+    fn generate(&self, optional: bool) -> TokenStream {
+        self.generate_router(optional, None)
+    }
+
+    /// Generates the routing expression of a `router!`, which records the arm
+    /// it takes against its `ROUTES` table for `route_match::testing`
+    fn generate_router(&self, optional: bool, table: Option<&TokenStream>) -> TokenStream {
+        let Internals { _routed, .. } = internals();
+        let routes = self.arms.iter().filter_map(|arm| match arm {
+            MatchArm::Route(route) => Some(route),
+            _ => None,
+        });
+        let uses_host = routes.clone().any(|route| route.host.is_some());
+        let uses_headers = routes.clone().any(|route| !route.headers.is_empty());
+        let method_and_path = &self.arg.generate(uses_host, uses_headers);
+        let captures: Vec<Ident> = self
+            .arms
+            .iter()
+            .flat_map(|arm| match arm {
+                MatchArm::Route(route) => route.captures(),
+                MatchArm::Pattern(_) => vec![],
+                MatchArm::Default(_) => vec![],
+            })
+            .collect();
+        let span = trace::span(&captures);
+        let coverage = coverage::block(&self.arms);
         let conditionals: Vec<TokenStream> = self
             .arms
             .iter()
-            .map(|route| route.generate_conditional())
+            .enumerate()
+            .map(|(index, arm)| arm.generate_conditional(optional, index, table))
             .collect();
+        let fallthrough = if optional && !self.has_default() {
+            let trace = trace::fallthrough();
+            quote! {
+                else {
+                    #trace
+                    None
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let allow = allow_lints();
 
         quote! {
-            #[allow(unused_parens)]
             {
                 #method_and_path
-                #(#conditionals)else*
+                #span
+                #coverage
+                #allow
+                let #_routed = #(#conditionals)else* #fallthrough;
+                #_routed
             }
         }
     }
 }
 
-impl MatchArm {
-    pub fn generate_conditional(&self) -> TokenStream {
+/// Lints triggered by the shape of the generated code, rather than by the arms themselves.
+/// Attributes aren't allowed on expressions, so these are applied to a statement.
+pub fn allow_lints() -> TokenStream {
+    quote! {
+        #[allow(
+            unused_parens,
+            clippy::if_same_then_else,
+            clippy::let_and_return,
+            clippy::manual_map,
+            clippy::redundant_pattern_matching,
+            clippy::suspicious_else_formatting
+        )]
+    }
+}
+
+impl MatchArmCodegen for MatchArm {
+    fn generate_conditional(
+        &self,
+        optional: bool,
+        index: usize,
+        routes: Option<&TokenStream>,
+    ) -> TokenStream {
         match self {
-            MatchArm::Route(route) => route.generate_conditional(),
-            MatchArm::Default(expr) => quote_spanned! { expr.span() =>
-                {
-                    #expr
+            MatchArm::Route(route) => route.generate_conditional(optional, index, routes),
+            MatchArm::Pattern(pattern) => pattern.generate_conditional(optional, index, routes),
+            MatchArm::Default(expr) => {
+                let coverage = coverage::hit(index);
+                let taken = testing::taken(routes, index);
+                let trace = trace::default_arm(expr);
+                let expr = &trace::instrument(expr);
+                let expr = if optional {
+                    quote_spanned! { expr.span() => Some(#expr) }
+                } else {
+                    quote_spanned! { expr.span() => #expr }
+                };
+                quote_spanned! { expr.span() =>
+                    {
+                        #coverage
+                        #taken
+                        #trace
+                        #expr
+                    }
                 }
-            },
+            }
         }
     }
 }
@@ -1,37 +1,62 @@
-use proc_macro2::Span;
-use syn::parse::Parse;
-use syn::spanned::Spanned;
-use syn::Ident;
-use syn::Token;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use quote::quote_spanned;
+use route_match_syntax::method::Method;
+use route_match_syntax::method::STANDARD_METHODS;
+use syn::LitStr;
 
-#[derive(Debug)]
-pub enum Method {
-    Any(Span),
-    Named(Ident),
-    Param(Ident),
+use crate::internal::internals;
+use crate::internal::Internals;
+use crate::route::Rejection;
+
+pub trait MethodCodegen {
+    fn rejection(&self) -> Option<Rejection>;
 }
 
-impl Parse for Method {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        if input.peek(Token![_]) {
-            let token: Token![_] = input.parse()?;
-            return Ok(Method::Any(token.span()));
-        } else if input.peek(Token![:]) {
-            let _: Token![:] = input.parse()?;
-            let ident: Ident = input.parse()?;
-            return Ok(Method::Param(ident));
-        }
-        let ident: Ident = input.parse()?;
-        Ok(Method::Named(ident))
+impl MethodCodegen for Method {
+    /// Rejects requests whose method doesn't match, comparing it through
+    /// `MatchMethod` so that it can be a string or a method type
+    fn rejection(&self) -> Option<Rejection> {
+        let Internals { _method, .. } = internals();
+        let (name, span) = match self {
+            Method::Any(_) => return None,
+            Method::Param(_) => return None,
+            Method::Named(method) => (method.to_string(), method.span()),
+            Method::Extension(method) => (method.value(), method.span()),
+        };
+
+        let method_str = LitStr::new(&name, span);
+        let found = request_method_str();
+        let mismatch = quote_spanned! { span =>
+            ::route_match::Mismatch::Method {
+                expected: #method_str,
+                found: ::std::string::ToString::to_string(#found),
+            }
+        };
+
+        let condition = match self {
+            Method::Named(_) if STANDARD_METHODS.contains(&name.as_str()) => {
+                let mut variant = name.to_ascii_lowercase();
+                variant[..1].make_ascii_uppercase();
+                let variant = format_ident!("{}", variant, span = span);
+                quote_spanned! { span =>
+                    !::route_match::MatchMethod::is(&#_method, ::route_match::Method::#variant)
+                }
+            }
+            _ => quote_spanned! { span =>
+                !::route_match::MatchMethod::is_named(&#_method, #method_str)
+            },
+        };
+        Some(Rejection {
+            condition,
+            mismatch,
+        })
     }
 }
 
-impl Method {
-    pub fn span(&self) -> Span {
-        match self {
-            Self::Any(span) => span.clone(),
-            Self::Named(method) => method.span().clone(),
-            Self::Param(method) => method.span().clone(),
-        }
-    }
+/// Converts the request method to a `&str`, e.g. to match a `RoutePattern`
+pub fn request_method_str() -> TokenStream {
+    let Internals { _method, .. } = internals();
+    quote! { ::route_match::MatchMethod::as_str(&#_method) }
 }
@@ -0,0 +1,205 @@
+use std::cmp::Reverse;
+
+use route_match_syntax::host::Host;
+use route_match_syntax::host::HostLabel;
+use route_match_syntax::match_stmnt::MatchArm;
+use route_match_syntax::method::Method;
+use route_match_syntax::path::PathComponent;
+use route_match_syntax::route::ArmKind;
+use route_match_syntax::route::Route;
+
+// With `#![order = specific]`, arms are sorted by how specific their patterns
+// are, comparing the path segment by segment (static segments before params,
+// params before rest), then the host, then the method (named before `_`), and
+// then the number of header predicates.  The sort is stable, so arms which are
+// equally specific stay in the order they're written.
+
+/// Sort key for an arm, where more specific patterns compare as less
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Specificity {
+    path: Vec<u8>,
+    host: Vec<u8>,
+    method: u8,
+    headers: Reverse<usize>,
+}
+
+impl Specificity {
+    fn of(route: &Route) -> Self {
+        Self {
+            path: route.path.components.iter().map(component_rank).collect(),
+            host: match &route.host {
+                Some(host) => host.labels.iter().map(label_rank).collect(),
+                None => vec![u8::MAX],
+            },
+            method: match route.method {
+                Method::Named(_) | Method::Extension(_) => 0,
+                Method::Param(_) | Method::Any(_) => 1,
+            },
+            headers: Reverse(route.headers.len()),
+        }
+    }
+}
+
+fn component_rank(component: &PathComponent) -> u8 {
+    match component {
+        PathComponent::Ident(_) | PathComponent::Quoted(_) => 0,
+        PathComponent::Extension { stem, .. } => match stem.as_ref() {
+            PathComponent::Ident(_) | PathComponent::Quoted(_) => 1,
+            _ => 2,
+        },
+        PathComponent::Param(_) | PathComponent::Wildcard(_) => 3,
+        PathComponent::Rest(_, _) => 4,
+        PathComponent::Any(_) => 5,
+    }
+}
+
+fn label_rank(label: &HostLabel) -> u8 {
+    match label {
+        HostLabel::Name(_, _) => 0,
+        HostLabel::Param(_) | HostLabel::Any(_) => 1,
+    }
+}
+
+/// Sorts the route arms from most to least specific, leaving the default arm
+/// last, and returns the position each arm was written at.  Equally specific
+/// arms which could match the same request are reported as ambiguous.
+pub fn sort_by_specificity(arms: &mut Vec<MatchArm>) -> syn::Result<Vec<usize>> {
+    for arm in arms.iter() {
+        if let MatchArm::Pattern(pattern) = arm {
+            return Err(syn::Error::new_spanned(
+                &pattern.pattern,
+                "`use` arms can't be sorted with `#![order = specific]`, since their patterns aren't known until runtime",
+            ));
+        }
+    }
+
+    let routes_end = match arms.last() {
+        Some(MatchArm::Default(_)) => arms.len() - 1,
+        _ => arms.len(),
+    };
+    let mut positions: Vec<usize> = (0..arms.len()).collect();
+    positions[..routes_end].sort_by_cached_key(|&i| match &arms[i] {
+        MatchArm::Route(route) => Specificity::of(route),
+        _ => unreachable!("only route arms are sorted"),
+    });
+    let mut unsorted: Vec<Option<MatchArm>> = arms.drain(..).map(Some).collect();
+    arms.extend(positions.iter().map(|&i| unsorted[i].take().unwrap()));
+    let routes = &arms[..routes_end];
+
+    let mut errors: Option<syn::Error> = None;
+    for (i, first) in routes.iter().enumerate() {
+        for second in &routes[i + 1..] {
+            let (MatchArm::Route(first), MatchArm::Route(second)) = (first, second) else {
+                continue;
+            };
+            if Specificity::of(first) != Specificity::of(second) || !ambiguous(first, second) {
+                continue;
+            }
+            let mut error = syn::Error::new(
+                second.method.span(),
+                "this arm is ambiguous: an equally specific arm matches the same requests",
+            );
+            error.combine(syn::Error::new(
+                first.method.span(),
+                "the arm it conflicts with is here",
+            ));
+            match &mut errors {
+                Some(errors) => errors.combine(error),
+                None => errors = Some(error),
+            }
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(positions),
+    }
+}
+
+/// Whether two equally specific arms can match the same request.  Arms with
+/// different header predicates are left in the order they're written, so
+/// content negotiation still works, as are `mount` and `try` arms, which
+/// continue to the next arm if they return `None`.
+fn ambiguous(first: &Route, second: &Route) -> bool {
+    if first.kind != ArmKind::Expr || second.kind != ArmKind::Expr {
+        return false;
+    }
+
+    let headers = |route: &Route| -> Vec<(String, String)> {
+        let mut headers: Vec<(String, String)> = route
+            .headers
+            .iter()
+            .map(|header| (header.name.clone(), header.value.clone()))
+            .collect();
+        headers.sort();
+        headers
+    };
+    if headers(first) != headers(second) {
+        return false;
+    }
+
+    let methods_overlap = match (method_name(&first.method), method_name(&second.method)) {
+        (Some(first), Some(second)) => first == second,
+        _ => true,
+    };
+    let hosts_overlap = match (&first.host, &second.host) {
+        (Some(first), Some(second)) => hosts_overlap(first, second),
+        _ => true,
+    };
+    let paths_overlap = first
+        .path
+        .components
+        .iter()
+        .zip(&second.path.components)
+        .all(|(first, second)| components_overlap(first, second));
+
+    methods_overlap && hosts_overlap && paths_overlap
+}
+
+fn method_name(method: &Method) -> Option<String> {
+    match method {
+        Method::Named(name) => Some(name.to_string()),
+        Method::Extension(name) => Some(name.value()),
+        Method::Param(_) | Method::Any(_) => None,
+    }
+}
+
+fn hosts_overlap(first: &Host, second: &Host) -> bool {
+    first
+        .labels
+        .iter()
+        .zip(&second.labels)
+        .all(|labels| match labels {
+            (HostLabel::Name(first, _), HostLabel::Name(second, _)) => {
+                first.eq_ignore_ascii_case(second)
+            }
+            _ => true,
+        })
+}
+
+fn components_overlap(first: &PathComponent, second: &PathComponent) -> bool {
+    match (first, second) {
+        (
+            PathComponent::Ident(_) | PathComponent::Quoted(_),
+            PathComponent::Ident(_) | PathComponent::Quoted(_),
+        ) => first.template() == second.template(),
+        (
+            PathComponent::Extension {
+                stem: first_stem,
+                extensions: first_extensions,
+                ..
+            },
+            PathComponent::Extension {
+                stem: second_stem,
+                extensions: second_extensions,
+                ..
+            },
+        ) => {
+            components_overlap(first_stem, second_stem)
+                && first_extensions
+                    .iter()
+                    .any(|extension| second_extensions.contains(extension))
+        }
+        _ => true,
+    }
+}
@@ -0,0 +1,228 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use quote::quote_spanned;
+use route_match_syntax::method::Method;
+use route_match_syntax::path::PathComponent;
+use route_match_syntax::pattern::PatternArm;
+use route_match_syntax::route::Route;
+use syn::parse::Parse;
+use syn::parse_quote;
+use syn::spanned::Spanned;
+use syn::Expr;
+use syn::LitStr;
+use syn::Token;
+
+use crate::coverage;
+use crate::internal::internals;
+use crate::internal::Internals;
+use crate::match_stmnt::allow_lints;
+use crate::match_stmnt::generate_component_args;
+use crate::method;
+use crate::route::RouteCodegen;
+use crate::testing;
+use crate::trace;
+
+/// Parses a standalone `METHOD /path` pattern, which isn't followed by an arm expression
+fn parse_pattern(input: syn::parse::ParseStream) -> syn::Result<Route> {
+    let route = Route::parse_pattern(input)?;
+    route.method.validate(&[])?;
+    route.validate_captures()?;
+    if let Some(host) = &route.host {
+        return Err(syn::Error::new(
+            host.span(),
+            "host patterns can only be used in a `route!` block with a host to match against",
+        ));
+    }
+    if let Some(header) = route.headers.first() {
+        return Err(syn::Error::new(
+            header.span,
+            "header predicates can only be used in a `route!` block with headers to match against",
+        ));
+    }
+    if let Some(PathComponent::Any(span)) = route.path.components.first() {
+        if let Method::Any(_) = route.method {
+            return Err(syn::Error::new(
+                *span,
+                "`_` matches every request; expected a route pattern",
+            ));
+        }
+    }
+    Ok(route)
+}
+
+/// The input to `route_matches!`: `method, path, PATTERN`, optionally followed
+/// by `=> expr` to evaluate with the captures bound
+#[derive(Debug)]
+pub struct RouteMatches {
+    method_provider: Expr,
+    path_provider: Expr,
+    route: Route,
+    binds: bool,
+}
+
+impl Parse for RouteMatches {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let method_provider: Expr = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let path_provider: Expr = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let mut route = parse_pattern(input)?;
+        let mut binds = false;
+        if input.peek(Token![=>]) {
+            let _: Token![=>] = input.parse()?;
+            route.expr = input.parse()?;
+            binds = true;
+        }
+        if input.peek(Token![,]) {
+            let _: Token![,] = input.parse()?;
+        }
+        Ok(Self {
+            method_provider,
+            path_provider,
+            route,
+            binds,
+        })
+    }
+}
+
+impl RouteMatches {
+    pub fn generate(&self) -> TokenStream {
+        let Internals { _matched, .. } = internals();
+        let method_and_path = generate_component_args(&self.method_provider, &self.path_provider);
+        let condition = self.route.condition();
+        let allow = allow_lints();
+
+        if !self.binds {
+            return quote! {
+                {
+                    #method_and_path
+                    #allow
+                    let #_matched = {
+                        #condition
+                    };
+                    #_matched.is_some()
+                }
+            };
+        }
+
+        let args = self.route.args();
+        let expr = &self.route.expr;
+        quote! {
+            {
+                #method_and_path
+                #allow
+                let #_matched = if let Some((#args)) = {
+                    #condition
+                } {
+                    Some(#expr)
+                } else {
+                    None
+                };
+                #_matched
+            }
+        }
+    }
+}
+
+/// The input to `route_pattern!`: a single `METHOD /path` pattern
+#[derive(Debug)]
+pub struct RoutePatternDef {
+    route: Route,
+}
+
+impl Parse for RoutePatternDef {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let route = parse_pattern(input)?;
+        Ok(Self { route })
+    }
+}
+
+impl RoutePatternDef {
+    /// Generates a `RoutePattern`, wrapping a matcher function so the pattern
+    /// can be stored in a `const` or `static`
+    pub fn generate(&self) -> TokenStream {
+        let route = &self.route;
+        let method_template = route.method.template();
+        let path_template = route.path.template();
+        let method_and_path = generate_component_args(&parse_quote!(&method), &parse_quote!(&path));
+        let condition = route.condition();
+        let args = route.args();
+        let captures = route.captures();
+        let capture_names: Vec<LitStr> = captures
+            .iter()
+            .map(|capture| LitStr::new(&capture.to_string(), capture.span()))
+            .collect();
+        // The method param is bound to `&&str`, while path captures are `&str`
+        let capture_values: Vec<TokenStream> = captures
+            .iter()
+            .map(|capture| match &route.method {
+                Method::Param(param) if param == capture => quote! { *#capture },
+                _ => quote! { #capture },
+            })
+            .collect();
+        let allow = allow_lints();
+
+        quote_spanned! { route.span() =>
+            {
+                #allow
+                fn matcher<'a>(method: &'a str, path: &'a str) -> Option<::std::vec::Vec<&'a str>> {
+                    #method_and_path
+                    let (#args) = {
+                        #condition
+                    }?;
+                    Some(::std::vec![#(#capture_values),*])
+                }
+                ::route_match::RoutePattern::new(
+                    #method_template,
+                    #path_template,
+                    &[#(#capture_names),*],
+                    matcher,
+                )
+            }
+        }
+    }
+}
+
+pub trait PatternArmCodegen {
+    fn generate_conditional(
+        &self,
+        optional: bool,
+        index: usize,
+        routes: Option<&TokenStream>,
+    ) -> TokenStream;
+}
+
+impl PatternArmCodegen for PatternArm {
+    fn generate_conditional(
+        &self,
+        optional: bool,
+        index: usize,
+        routes: Option<&TokenStream>,
+    ) -> TokenStream {
+        let Internals { _path_str, .. } = internals();
+        let pattern = &self.pattern;
+        let binding = match &self.binding {
+            Some(binding) => quote! { #binding },
+            None => quote! { _ },
+        };
+        let expr = &trace::instrument(&self.expr);
+        let expr = if optional {
+            quote_spanned! { expr.span() => Some(#expr) }
+        } else {
+            quote_spanned! { expr.span() => #expr }
+        };
+        let coverage = coverage::hit(index);
+        let taken = testing::taken(routes, index);
+        let trace = trace::pattern_arm(self);
+        let method = method::request_method_str();
+
+        quote_spanned! { pattern.span() =>
+            if let Some(#binding) = ::route_match::RoutePattern::captures(&#pattern, #method, #_path_str) {
+                #coverage
+                #taken
+                #trace
+                #expr
+            }
+        }
+    }
+}
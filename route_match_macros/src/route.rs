@@ -1,92 +1,137 @@
-use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use quote::quote;
 use quote::quote_spanned;
-use syn::parse::Parse;
+use route_match_syntax::header::HeaderPredicate;
+use route_match_syntax::method::Method;
+use route_match_syntax::path::PathComponent;
+use route_match_syntax::route::ArmKind;
+use route_match_syntax::route::Route;
 use syn::spanned::Spanned;
-use syn::Expr;
-use syn::Ident;
 use syn::LitStr;
-use syn::Token;
 
-use crate::method::Method;
-use crate::path::Path;
-use crate::path::PathComponent;
+use crate::coverage;
+use crate::header::HeaderCodegen;
+use crate::host::HostCodegen;
+use crate::internal::internals;
+use crate::internal::Internals;
+use crate::method::MethodCodegen;
+use crate::testing;
+use crate::trace;
 
-#[derive(Debug)]
-pub struct Route {
-    pub method: Method,
-    pub path: Path,
-    pub expr: Expr,
+/// A check a request has to pass to match a route
+pub struct Rejection {
+    /// Evaluates to `true` if the request does *not* match
+    pub condition: TokenStream,
+    /// Evaluates to the `route_match::Mismatch` describing the failed check,
+    /// for `route_explain!`
+    pub mismatch: TokenStream,
 }
 
-impl Parse for Route {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let method: Method = input.parse()?;
-        let path: Path = input.parse()?;
-        let _: Token![=>] = input.parse()?;
-        let expr: Expr = input.parse()?;
-        Ok(Route { method, path, expr })
-    }
+pub trait RouteCodegen {
+    fn rejections(&self) -> Vec<Rejection>;
+    fn condition(&self) -> TokenStream;
+    fn generate_conditional(
+        &self,
+        optional: bool,
+        index: usize,
+        routes: Option<&TokenStream>,
+    ) -> TokenStream;
+    fn args(&self) -> TokenStream;
+    fn static_conditions(&self) -> Vec<Rejection>;
+    fn arg_assignments(&self) -> TokenStream;
 }
 
-impl Route {
-    pub fn span(&self) -> Span {
-        self.method
-            .span()
-            .join(self.expr.span())
-            .unwrap_or(self.expr.span().clone())
-    }
-
-    pub fn method_condition(&self) -> TokenStream {
-        let method = &self.method;
-        match method {
-            Method::Any(_) => quote! {},
-            Method::Named(method) => {
-                let method_str = LitStr::new(&method.to_string(), method.span());
-                let method_condition = quote_spanned! { method.span() =>
-                    if _method != &#method_str {
-                        // If the method doesn't match, return None
-                        None
-                    }
-                };
-                method_condition
-            }
-            Method::Param(_) => quote! {},
+impl RouteCodegen for Route {
+    /// The checks a request has to pass to match this route, in order
+    fn rejections(&self) -> Vec<Rejection> {
+        let mut rejections: Vec<Rejection> = vec![];
+        if let Some(rejection) = self.method.rejection() {
+            rejections.push(rejection);
+        }
+        if let Some(host) = &self.host {
+            rejections.extend(host.rejections());
         }
+        rejections.extend(path_rejections(self));
+        rejections.extend(self.headers.iter().map(HeaderPredicate::rejection));
+        rejections
     }
 
-    pub fn generate_conditional(&self) -> TokenStream {
+    /// Generates an expression which evaluates to `Some((captures...))` if the
+    /// request matches this route, or `None` otherwise
+    fn condition(&self) -> TokenStream {
         let args = self.args();
-
-        let expr = &self.expr;
-        let expr = quote_spanned! { expr.span() =>
-            #expr
-        };
         let arg_assignments = self.arg_assignments();
-        let match_conditions = self.match_conditions();
+        let rejections = self
+            .rejections()
+            .into_iter()
+            .map(|rejection| rejection.condition);
 
-        let method_condition = self.method_condition();
+        quote_spanned! { self.span() =>
+            #(if #rejections {
+                None
+            } else)* {
+                #arg_assignments
+                Some((#args))
+            }
+        }
+    }
 
-        let arg_clause = quote_spanned! { self.path.span() =>
-            #arg_assignments
-            Some((#args))
+    fn generate_conditional(
+        &self,
+        optional: bool,
+        index: usize,
+        routes: Option<&TokenStream>,
+    ) -> TokenStream {
+        let args = self.args();
+        let coverage = coverage::hit(index);
+        let taken = testing::taken(routes, index);
+        let coverage = quote! {
+            #coverage
+            #taken
         };
+        let trace = trace::route_arm(self);
+        let condition = self.condition();
 
-        let path_conditions = quote_spanned! { self.path.span() =>
-            #match_conditions
-            else {
-                // Otherwise we assign the args and return them
-                #arg_clause
+        let expr = &trace::instrument(&self.expr);
+        match self.kind {
+            ArmKind::Mount => {
+                let mount = generate_mount(self);
+                return generate_delegate(
+                    self,
+                    quote! { _ },
+                    condition,
+                    trace,
+                    coverage,
+                    mount,
+                    optional,
+                );
             }
+            ArmKind::Try => {
+                let expr = quote_spanned! { expr.span() => #expr };
+                return generate_delegate(
+                    self,
+                    quote! { (#args) },
+                    condition,
+                    trace,
+                    coverage,
+                    expr,
+                    optional,
+                );
+            }
+            ArmKind::Expr => {}
+        }
+        let trace = quote! {
+            #coverage
+            #trace
         };
 
-        let condition = if !self.requires_method_match() && self.matches_any_path() {
-            arg_clause
+        let expr = if optional {
+            quote_spanned! { expr.span() =>
+                Some(#expr)
+            }
         } else {
-            quote_spanned! { self.span() =>
-                #method_condition
-                #path_conditions
+            quote_spanned! { expr.span() =>
+                #expr
             }
         };
 
@@ -94,95 +139,16 @@ impl Route {
             if let Some((#args)) = {
                 #condition
             } {
+                #trace
                 #expr
             }
         }
     }
 
-    fn match_conditions(&self) -> TokenStream {
-        let leading_token = match self.method {
-            Method::Any(_) => quote! {},
-            Method::Param(_) => quote! {},
-            Method::Named(_) => quote! { else },
-        };
-
-        if self.matches_any_path() {
-            return quote! {};
-        } else if self.has_indeterminate_length() {
-            let static_conditions = self.static_conditions();
-            quote_spanned! { self.path.span() =>
-                #leading_token if #static_conditions {
-                    // If any of the satic conditions don't match, return None
-                    None
-                }
-            }
-        } else {
-            let static_conditions = self.static_conditions();
-            let count = self.path.components.len();
-            quote_spanned! { self.path.span() =>
-                #leading_token if _path.len() != #count {
-                    // If the path comoponent doesn't match, return None
-                    None
-                } else if #static_conditions {
-                    // If any of the satic conditions don't match, return None
-                    None
-                }
-            }
-        }
-    }
-
-    fn has_indeterminate_length(&self) -> bool {
-        return (&self).path.components.iter().fold(false, |acc, cmp| {
-            if let PathComponent::Wildcard(_) = cmp {
-                true
-            } else if let PathComponent::Rest(_, _) = cmp {
-                true
-            } else if let PathComponent::Any(_) = cmp {
-                true
-            } else {
-                acc
-            }
-        });
-    }
-
-    fn matches_any_path(&self) -> bool {
-        return (&self).path.components.iter().fold(false, |acc, cmp| {
-            if let PathComponent::Any(_) = cmp {
-                true
-            } else {
-                acc
-            }
-        });
-    }
-
-    fn requires_method_match(&self) -> bool {
-        if let Method::Named(_) = &self.method {
-            return true;
-        }
-        false
-    }
-
     fn args(&self) -> TokenStream {
-        let mut args: Vec<Ident> = (&self)
-            .path
-            .components
-            .iter()
-            .filter_map(|component| {
-                if let PathComponent::Param(param) = component {
-                    Some(param.clone())
-                } else if let PathComponent::Rest(_, Some(param)) = component {
-                    Some(param.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        if let Method::Param(param) = &self.method {
-            args.push(param.clone());
-        }
+        let args = self.captures();
 
-        if args.len() == 0 {
+        if args.is_empty() {
             return quote! {
                 ()
             };
@@ -193,66 +159,121 @@ impl Route {
         }
     }
 
-    pub fn static_conditions(&self) -> TokenStream {
-        let mut static_conditions: Vec<TokenStream> = vec![];
+    fn static_conditions(&self) -> Vec<Rejection> {
+        let Internals { _path, _stem, .. } = internals();
+        let mut static_conditions: Vec<Rejection> = vec![];
+        let segment_mismatch = |i: usize, expected: TokenStream| {
+            quote! {
+                ::route_match::Mismatch::Segment {
+                    index: #i,
+                    expected: #expected,
+                    found: ::std::string::ToString::to_string(#_path[#i]),
+                }
+            }
+        };
 
         for i in 0..self.path.components.len() {
             match &self.path.components[i] {
                 PathComponent::Ident(name) => {
-                    static_conditions.push(quote_spanned! { name.span() =>
-                        _path[#i] != stringify!(#name)
+                    static_conditions.push(Rejection {
+                        condition: quote_spanned! { name.span() =>
+                            #_path[#i] != stringify!(#name)
+                        },
+                        mismatch: segment_mismatch(i, quote! { stringify!(#name) }),
+                    });
+                }
+                PathComponent::Quoted(segment) => {
+                    static_conditions.push(Rejection {
+                        condition: quote_spanned! { segment.span() =>
+                            #_path[#i] != #segment
+                        },
+                        mismatch: segment_mismatch(i, quote! { #segment }),
                     });
                 }
                 PathComponent::Param(_) => {}
                 PathComponent::Rest(_, _) => {}
                 PathComponent::Wildcard(_) => {}
                 PathComponent::Any(_) => {}
+                component @ PathComponent::Extension {
+                    stem, extensions, ..
+                } => {
+                    let stem_condition = match stem.as_ref() {
+                        PathComponent::Ident(name) => quote! { #_stem == stringify!(#name) },
+                        PathComponent::Quoted(segment) => quote! { #_stem == #segment },
+                        _ => quote! { !#_stem.is_empty() },
+                    };
+                    let extensions = extensions
+                        .iter()
+                        .map(|extension| LitStr::new(&extension.to_string(), extension.span()));
+                    let expected = component.template();
+                    static_conditions.push(Rejection {
+                        condition: quote_spanned! { stem.span() =>
+                            !matches!(
+                                #_path[#i].rsplit_once('.'),
+                                Some((#_stem, #(#extensions)|*)) if #stem_condition
+                            )
+                        },
+                        mismatch: segment_mismatch(i, quote! { #expected }),
+                    });
+                }
             }
         }
 
-        quote! {
-            #(#static_conditions)||*
-        }
+        static_conditions
     }
 
-    pub fn arg_assignments(&self) -> TokenStream {
+    fn arg_assignments(&self) -> TokenStream {
+        let Internals {
+            _method,
+            _path_str,
+            _path,
+            byte_offset,
+            ..
+        } = internals();
         let mut assignments: Vec<TokenStream> = vec![];
 
+        if let Some(host) = &self.host {
+            assignments.push(host.arg_assignments());
+        }
+
         for i in 0..self.path.components.len() {
             match &self.path.components[i] {
-                PathComponent::Ident(_) => {}
+                PathComponent::Ident(_) | PathComponent::Quoted(_) => {}
                 PathComponent::Param(name) => {
                     assignments.push(quote_spanned! { name.span() =>
-                        let #name = _path[#i];
+                        let #name = #_path[#i];
                     });
                 }
                 PathComponent::Rest(_, Some(name)) => {
+                    let rest_offset = rest_offset(i);
                     let assignment = quote_spanned! { name.span() =>
-                        let mut byte_offset = 0;
-                        let mut segment_count = 0;
-
-                        for (idx, c) in _path_str.char_indices() {
-                            if c == '/' {
-                                if segment_count == #i {
-                                    byte_offset = idx + 1; // Skip past the '/'
-                                    break;
-                                }
-                                segment_count += 1;
-                            }
-                        }
-                        let #name = &_path_str[byte_offset..];
+                        #rest_offset
+                        let #name = &#_path_str[#byte_offset..];
                     };
                     assignments.push(assignment);
                 }
                 PathComponent::Rest(_, _) => {}
                 PathComponent::Wildcard(_) => {}
                 PathComponent::Any(_) => {}
+                PathComponent::Extension { stem, binding, .. } => {
+                    let stem = match stem.as_ref() {
+                        PathComponent::Param(name) => quote! { #name },
+                        _ => quote! { _ },
+                    };
+                    let binding = match binding {
+                        Some(name) => quote! { #name },
+                        None => quote! { _ },
+                    };
+                    assignments.push(quote! {
+                        let (#stem, #binding) = #_path[#i].rsplit_once('.').unwrap_or_default();
+                    });
+                }
             }
         }
 
         if let Method::Param(name) = &self.method {
             assignments.push(quote! {
-                let #name = _method;
+                let #name = #_method;
             });
         }
 
@@ -261,3 +282,135 @@ impl Route {
         }
     }
 }
+
+/// A delegating arm is only evaluated once its pattern matches, and its
+/// `Option` result decides whether dispatch continues with the next arm.  It
+/// only counts as taken, running `taken`, once it returns `Some`.
+fn generate_delegate(
+    route: &Route,
+    args: TokenStream,
+    condition: TokenStream,
+    trace: TokenStream,
+    taken: TokenStream,
+    body: TokenStream,
+    optional: bool,
+) -> TokenStream {
+    let Internals { _delegated, .. } = internals();
+    let delegated = if optional {
+        quote! { Some(#_delegated) }
+    } else {
+        quote! { #_delegated }
+    };
+
+    quote_spanned! { route.span() =>
+        if let Some(#_delegated) = {
+            if let Some(#args) = {
+                #condition
+            } {
+                #trace
+                #body
+            } else {
+                None
+            }
+        } {
+            #taken
+            #delegated
+        }
+    }
+}
+
+/// Calls a mounted router with the rest of the path following its prefix
+pub fn generate_mount(route: &Route) -> TokenStream {
+    let Internals {
+        _method,
+        _path_str,
+        _prefix,
+        byte_offset,
+        ..
+    } = internals();
+    let router = &route.expr;
+    let rest_index = route.path.components.len() - 1;
+    let rest_offset = rest_offset(rest_index);
+
+    quote! {
+        #rest_offset
+        let #_prefix = #_path_str[..#byte_offset].trim_end_matches('/');
+        (#router)(#_method, ::route_match::Mount::new(#_prefix, &#_path_str[#byte_offset..]))
+    }
+}
+
+/// Checks the path length first, so the static components can be indexed safely
+fn path_rejections(route: &Route) -> Vec<Rejection> {
+    let Internals { _path, .. } = internals();
+    if route.matches_any_path() {
+        return vec![];
+    }
+
+    let mut rejections: Vec<Rejection> = vec![];
+    if route.has_indeterminate_length() {
+        let min_count = route
+            .path
+            .components
+            .iter()
+            .filter(|component| !matches!(component, PathComponent::Rest(_, _)))
+            .count();
+        rejections.push(Rejection {
+            condition: quote_spanned! { route.path.span() =>
+                #_path.len() < #min_count
+            },
+            mismatch: quote! {
+                ::route_match::Mismatch::PathLength {
+                    expected: #min_count,
+                    at_least: true,
+                    found: #_path.len(),
+                }
+            },
+        });
+    } else {
+        let count = route.path.components.len();
+        rejections.push(Rejection {
+            condition: quote_spanned! { route.path.span() =>
+                #_path.len() != #count
+            },
+            mismatch: quote! {
+                ::route_match::Mismatch::PathLength {
+                    expected: #count,
+                    at_least: false,
+                    found: #_path.len(),
+                }
+            },
+        });
+    }
+    rejections.extend(route.static_conditions());
+    rejections
+}
+
+/// Finds the byte offset in `_path_str` where the path component at `index` starts,
+/// or the end of the path if there is no such component
+fn rest_offset(index: usize) -> TokenStream {
+    let Internals {
+        _path_str,
+        byte_offset,
+        segment_count,
+        in_segment,
+        ..
+    } = internals();
+    quote! {
+        let mut #byte_offset = #_path_str.len();
+        let mut #segment_count = 0;
+        let mut #in_segment = false;
+
+        for (idx, c) in #_path_str.char_indices() {
+            if c == '/' {
+                #in_segment = false;
+            } else if !#in_segment {
+                if #segment_count == #index {
+                    #byte_offset = idx;
+                    break;
+                }
+                #in_segment = true;
+                #segment_count += 1;
+            }
+        }
+    }
+}
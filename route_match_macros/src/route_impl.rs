@@ -1,14 +1,87 @@
 use proc_macro2::TokenStream;
+use quote::quote;
+use route_match_syntax::match_stmnt::MatchStmnt;
+use route_match_syntax::router::RouterDef;
 
-use crate::match_stmnt::MatchStmnt;
+use crate::explain;
+use crate::match_stmnt::MatchStmntCodegen;
+use crate::pattern::RouteMatches;
+use crate::pattern::RoutePatternDef;
+use crate::router;
 
 pub fn parse(input: TokenStream) -> TokenStream {
-    let stmnt: MatchStmnt = match syn::parse2::<MatchStmnt>(input) {
+    expand(input, false)
+}
+
+pub fn parse_optional(input: TokenStream) -> TokenStream {
+    expand(input, true)
+}
+
+fn expand(input: TokenStream, optional: bool) -> TokenStream {
+    let mut stmnt: MatchStmnt = match syn::parse2::<MatchStmnt>(input) {
         Ok(stmnt) => stmnt,
         Err(err) => {
-            return proc_macro2::TokenStream::from(err.to_compile_error());
+            return expr_errors(err);
         }
     };
 
-    stmnt.generate()
+    if let Err(err) = stmnt.validate(optional) {
+        return expr_errors(err);
+    }
+
+    if let Err(err) = stmnt.order_arms() {
+        return expr_errors(err);
+    }
+
+    stmnt.generate(optional)
+}
+
+/// Parses the same block as `route_opt!`, so a default arm isn't required
+pub fn parse_explain(input: TokenStream) -> TokenStream {
+    let mut stmnt: MatchStmnt = match syn::parse2::<MatchStmnt>(input) {
+        Ok(stmnt) => stmnt,
+        Err(err) => {
+            return expr_errors(err);
+        }
+    };
+
+    if let Err(err) = stmnt.validate(true) {
+        return expr_errors(err);
+    }
+
+    if let Err(err) = stmnt.order_arms() {
+        return expr_errors(err);
+    }
+
+    explain::generate(&stmnt)
+}
+
+pub fn parse_matches(input: TokenStream) -> TokenStream {
+    match syn::parse2::<RouteMatches>(input) {
+        Ok(matches) => matches.generate(),
+        Err(err) => expr_errors(err),
+    }
+}
+
+pub fn parse_pattern(input: TokenStream) -> TokenStream {
+    match syn::parse2::<RoutePatternDef>(input) {
+        Ok(pattern) => pattern.generate(),
+        Err(err) => expr_errors(err),
+    }
+}
+
+pub fn parse_router(input: TokenStream) -> TokenStream {
+    match syn::parse2::<RouterDef>(input) {
+        Ok(def) => router::generate(def),
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+/// Several combined errors expand to consecutive `compile_error!` calls,
+/// which have to be statements of a block in expression position
+fn expr_errors(err: syn::Error) -> TokenStream {
+    let errors = err.to_compile_error();
+    quote! {
+        { #errors }
+    }
 }
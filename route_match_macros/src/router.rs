@@ -0,0 +1,109 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use quote::quote_spanned;
+use route_match_syntax::match_stmnt::MatchArm;
+use route_match_syntax::route::Route;
+use route_match_syntax::router::RouterDef;
+use syn::ext::IdentExt;
+use syn::LitStr;
+
+use crate::match_stmnt::MatchStmntCodegen;
+
+/// Generates the function defined with `router!`, along with a module of the
+/// same name holding the table of its routes.  Without a default arm, the
+/// function returns `None` if no arm matches, as with `route_opt!`.
+pub fn generate(mut def: RouterDef) -> TokenStream {
+    let optional = !def.stmnt.has_default();
+    if let Err(err) = def.stmnt.validate(optional) {
+        return err.to_compile_error();
+    }
+    let positions = match def.stmnt.order_arms() {
+        Ok(positions) => positions,
+        Err(err) => return err.to_compile_error(),
+    };
+    let routes = route_table(&def.stmnt.arms, &positions);
+
+    let RouterDef {
+        attrs,
+        vis,
+        sig,
+        stmnt,
+    } = def;
+    let name = &sig.ident;
+    let body = stmnt.generate_router(optional, Some(&quote! { #name::ROUTES }));
+    let doc = format!("The routes matched by [`{}()`]", name.unraw());
+
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            #body
+        }
+
+        #[doc = #doc]
+        #vis mod #name {
+            pub const ROUTES: &[::route_match::RouteInfo] = &[#(#routes),*];
+        }
+    }
+}
+
+/// An entry for each arm of the block other than the default arm, in the order
+/// they're tried.  `positions` holds where each arm was written.
+fn route_table(arms: &[MatchArm], positions: &[usize]) -> Vec<TokenStream> {
+    arms.iter()
+        .zip(positions)
+        .filter_map(|(arm, &i)| match arm {
+            MatchArm::Route(route) => Some(route_info(route, i)),
+            MatchArm::Pattern(pattern) => {
+                let pattern = from_parent(&pattern.pattern);
+                Some(quote! {
+                    ::route_match::RouteInfo::from_pattern(&#pattern, #i)
+                })
+            }
+            MatchArm::Default(_) => None,
+        })
+        .collect()
+}
+
+/// The table is declared in a module beside the function, so a pattern named
+/// relative to the function's module is reached through `super`
+fn from_parent(path: &syn::Path) -> TokenStream {
+    let Some(first) = path.segments.first() else {
+        return quote! { #path };
+    };
+    if path.leading_colon.is_some() || first.ident == "crate" || first.ident == "$crate" {
+        return quote! { #path };
+    }
+    let span = first.ident.span();
+    if first.ident == "self" {
+        let rest = path.segments.iter().skip(1);
+        return quote_spanned! { span => super #(::#rest)* };
+    }
+    quote_spanned! { span => super::#path }
+}
+
+fn route_info(route: &Route, arm: usize) -> TokenStream {
+    let method = route.method.router_template();
+    let host = match &route.host {
+        Some(host) => {
+            let host = host.template();
+            quote! { Some(#host) }
+        }
+        None => quote! { None },
+    };
+    let path = route.path.template();
+    let headers = route.headers.iter().map(|header| header.template());
+    let captures = route.captures();
+    let capture_names = captures
+        .iter()
+        .map(|capture| LitStr::new(&capture.unraw().to_string(), capture.span()));
+    quote! {
+        ::route_match::RouteInfo::new(
+            #method,
+            #host,
+            #path,
+            &[#(#headers),*],
+            &[#(#capture_names),*],
+            #arm,
+        )
+    }
+}
@@ -0,0 +1,14 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Records a request taking the arm at `index` of a `router!`, whose table is
+/// at `routes`, for `route_match::testing` to report.  Expands to nothing
+/// unless the `testing` feature is enabled, or outside a `router!`.
+pub fn taken(routes: Option<&TokenStream>, index: usize) -> TokenStream {
+    match routes {
+        Some(routes) if cfg!(feature = "testing") => quote! {
+            ::route_match::testing::take_arm(#routes, #index);
+        },
+        _ => quote! {},
+    }
+}
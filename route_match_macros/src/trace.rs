@@ -0,0 +1,181 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use route_match_syntax::pattern::PatternArm;
+use route_match_syntax::route::Route;
+use syn::ext::IdentExt;
+use syn::parse_quote_spanned;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::visit_mut;
+use syn::visit_mut::VisitMut;
+use syn::Expr;
+use syn::Ident;
+
+use crate::internal::internals;
+use crate::internal::Internals;
+
+// Everything in this module expands to nothing unless the `tracing` feature
+// is enabled, in which case the generated code refers to the `tracing` crate
+// re-exported by `route_match`.
+
+/// Declares the request span, with an empty field for every capture in the block
+pub fn span(captures: &[Ident]) -> TokenStream {
+    if !cfg!(feature = "tracing") {
+        return quote! {};
+    }
+
+    let Internals { _method, _span, .. } = internals();
+    let mut fields: Vec<&Ident> = vec![];
+    for capture in captures {
+        if !fields.contains(&capture) {
+            fields.push(capture);
+        }
+    }
+
+    quote! {
+        let #_span = ::route_match::__private::tracing::info_span!(
+            "route",
+            http.method = %#_method,
+            http.route = ::route_match::__private::tracing::field::Empty,
+            #(#fields = ::route_match::__private::tracing::field::Empty,)*
+        );
+    }
+}
+
+/// Records the matched template and captures, and enters the span for the arm expression
+pub fn route_arm(route: &Route) -> TokenStream {
+    if !cfg!(feature = "tracing") {
+        return quote! {};
+    }
+
+    let Internals { _span, .. } = internals();
+    let template = route.path.template();
+    let records: Vec<TokenStream> = route
+        .captures()
+        .iter()
+        .map(|capture| {
+            let name = capture.unraw().to_string();
+            quote! {
+                #_span.record(#name, ::route_match::__private::tracing::field::display(&#capture));
+            }
+        })
+        .collect();
+    let enter = enter(&route.expr);
+
+    quote! {
+        #_span.record("http.route", #template);
+        #(#records)*
+        #enter
+    }
+}
+
+/// Records the template of a shared pattern, and enters the span for the arm expression
+pub fn pattern_arm(arm: &PatternArm) -> TokenStream {
+    if !cfg!(feature = "tracing") {
+        return quote! {};
+    }
+
+    let Internals { _span, .. } = internals();
+    let pattern = &arm.pattern;
+    let enter = enter(&arm.expr);
+
+    quote! {
+        #_span.record("http.route", ::route_match::RoutePattern::path(&#pattern));
+        #enter
+    }
+}
+
+/// Emits a debug event before falling through to the default arm
+pub fn default_arm(default: &Expr) -> TokenStream {
+    if !cfg!(feature = "tracing") {
+        return quote! {};
+    }
+
+    let fallthrough = fallthrough();
+    let enter = enter(default);
+
+    quote! {
+        #fallthrough
+        #enter
+    }
+}
+
+/// Emits a debug event when no route matched the request
+pub fn fallthrough() -> TokenStream {
+    if !cfg!(feature = "tracing") {
+        return quote! {};
+    }
+
+    let Internals {
+        _path_str, _span, ..
+    } = internals();
+    quote! {
+        ::route_match::__private::tracing::debug!(
+            parent: &#_span,
+            http.path = %#_path_str,
+            "no route matched, falling through to the default arm"
+        );
+    }
+}
+
+/// An entered span guard is not `Send`, so it must not be held across an
+/// `.await`. Arms which await are run through [`instrument`] instead.
+fn enter(expr: &Expr) -> TokenStream {
+    let Internals { _span, _enter, .. } = internals();
+    if contains_await(expr) {
+        return quote! {};
+    }
+    quote! {
+        let #_enter = #_span.enter();
+    }
+}
+
+/// Instruments every future the arm expression awaits with the request span,
+/// so events emitted by async handlers are nested under it
+pub fn instrument(expr: &Expr) -> Expr {
+    let mut expr = expr.clone();
+    if cfg!(feature = "tracing") {
+        InstrumentAwaits.visit_expr_mut(&mut expr);
+    }
+    expr
+}
+
+struct InstrumentAwaits;
+
+impl VisitMut for InstrumentAwaits {
+    fn visit_expr_await_mut(&mut self, expr: &mut syn::ExprAwait) {
+        visit_mut::visit_expr_await_mut(self, expr);
+        let Internals { _span, .. } = internals();
+        let base = &expr.base;
+        *expr.base = parse_quote_spanned! { base.span() =>
+            ::route_match::__private::tracing::Instrument::instrument(
+                ::core::future::IntoFuture::into_future(#base),
+                ::core::clone::Clone::clone(&#_span),
+            )
+        };
+    }
+
+    // Async blocks and closures aren't awaited by the arm itself
+    fn visit_expr_async_mut(&mut self, _: &mut syn::ExprAsync) {}
+    fn visit_expr_closure_mut(&mut self, _: &mut syn::ExprClosure) {}
+}
+
+fn contains_await(expr: &Expr) -> bool {
+    struct AwaitVisitor {
+        found: bool,
+    }
+
+    impl<'ast> Visit<'ast> for AwaitVisitor {
+        fn visit_expr_await(&mut self, _: &'ast syn::ExprAwait) {
+            self.found = true;
+        }
+
+        // Awaits nested in async blocks or closures don't suspend the arm itself
+        fn visit_expr_async(&mut self, _: &'ast syn::ExprAsync) {}
+        fn visit_expr_closure(&mut self, _: &'ast syn::ExprClosure) {}
+    }
+
+    let mut visitor = AwaitVisitor { found: false };
+    visitor.visit_expr(expr);
+    visitor.found
+}
@@ -0,0 +1,72 @@
+use proc_macro2::Span;
+use proc_macro2::TokenTree;
+use syn::bracketed;
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::Ident;
+use syn::LitStr;
+use syn::Token;
+
+/// A single header predicate, e.g. `content-type: multipart/*`
+#[derive(Debug)]
+pub struct HeaderPredicate {
+    pub name: String,
+    pub value: String,
+    pub span: Span,
+}
+
+impl Parse for HeaderPredicate {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Header names are hyphenated, and are matched in lowercase
+        let first = Ident::parse_any(input)?;
+        let mut name = first.to_string().to_ascii_lowercase();
+        while input.peek(Token![-]) {
+            let _: Token![-] = input.parse()?;
+            let part = Ident::parse_any(input)?;
+            name.push('-');
+            name.push_str(&part.to_string().to_ascii_lowercase());
+        }
+        let _: Token![:] = input.parse()?;
+
+        // The value is either a string literal, or the tokens up to the next `,`
+        // glued together, so `application/vnd.api+json` can be written unquoted
+        let value = if input.peek(LitStr) {
+            let value: LitStr = input.parse()?;
+            value.value()
+        } else {
+            let mut value = String::new();
+            while !input.is_empty() && !input.peek(Token![,]) {
+                let token: TokenTree = input.parse()?;
+                value.push_str(&token.to_string());
+            }
+            value
+        };
+        if value.is_empty() {
+            return Err(syn::Error::new(
+                first.span(),
+                format!("expected a value for the `{}` header", name),
+            ));
+        }
+
+        Ok(Self {
+            name,
+            value,
+            span: first.span(),
+        })
+    }
+}
+
+impl HeaderPredicate {
+    /// Renders the predicate as it matches, e.g. `content-type: multipart/*`
+    pub fn template(&self) -> String {
+        format!("{}: {}", self.name, self.value)
+    }
+
+    /// Parses the `[name: value, ...]` list following a path
+    pub fn parse_list(input: syn::parse::ParseStream) -> syn::Result<Vec<Self>> {
+        let content;
+        bracketed!(content in input);
+        let predicates = content.parse_terminated(Self::parse, Token![,])?;
+        Ok(predicates.into_iter().collect())
+    }
+}
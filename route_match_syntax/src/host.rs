@@ -0,0 +1,105 @@
+use proc_macro2::Span;
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::Ident;
+use syn::Token;
+
+/// A single label of a host pattern, e.g. `api`, `:tenant` or `_`
+#[derive(Debug)]
+pub enum HostLabel {
+    Name(String, Span),
+    Param(Ident),
+    Any(Span),
+}
+
+impl Parse for HostLabel {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![:]) {
+            let _: Token![:] = input.parse()?;
+            let name = Ident::parse_any(input)?;
+            return Ok(HostLabel::Param(name));
+        } else if input.peek(Token![_]) {
+            let token: Token![_] = input.parse()?;
+            return Ok(HostLabel::Any(token.span));
+        }
+
+        // Labels may be keywords (`static.example.com`) or contain hyphens
+        let first = Ident::parse_any(input)?;
+        let mut name = first.to_string();
+        while input.peek(Token![-]) {
+            let _: Token![-] = input.parse()?;
+            let part = Ident::parse_any(input)?;
+            name.push('-');
+            name.push_str(&part.to_string());
+        }
+        Ok(HostLabel::Name(name, first.span()))
+    }
+}
+
+/// The host part of a route pattern, e.g. `:tenant.example.com`
+#[derive(Debug)]
+pub struct Host {
+    pub labels: Vec<HostLabel>,
+}
+
+impl Parse for Host {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut labels: Vec<HostLabel> = vec![input.parse()?];
+        while input.peek(Token![.]) && !input.peek(Token![..]) {
+            let _: Token![.] = input.parse()?;
+            labels.push(input.parse()?);
+        }
+        Ok(Self { labels })
+    }
+}
+
+impl Host {
+    /// A host starts with a label, where a path would start with `/`, `..` or a lone `_`
+    pub fn peek(input: syn::parse::ParseStream) -> bool {
+        if input.peek(Token![_]) {
+            return input.peek2(Token![.]);
+        }
+        input.peek(Ident::peek_any) || input.peek(Token![:])
+    }
+
+    pub fn span(&self) -> Span {
+        let Some(first) = self.labels.first() else {
+            return Span::call_site();
+        };
+        first.span()
+    }
+
+    /// Renders the host as it was written in the pattern, e.g. `:tenant.example.com`
+    pub fn template(&self) -> String {
+        let labels: Vec<String> = self
+            .labels
+            .iter()
+            .map(|label| match label {
+                HostLabel::Name(name, _) => name.clone(),
+                HostLabel::Param(param) => format!(":{}", param.unraw()),
+                HostLabel::Any(_) => "_".to_string(),
+            })
+            .collect();
+        labels.join(".")
+    }
+
+    pub fn captures(&self) -> Vec<Ident> {
+        self.labels
+            .iter()
+            .filter_map(|label| match label {
+                HostLabel::Param(param) => Some(param.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl HostLabel {
+    pub fn span(&self) -> Span {
+        match self {
+            HostLabel::Name(_, span) => *span,
+            HostLabel::Param(param) => param.span(),
+            HostLabel::Any(span) => *span,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! The syntax tree and parser for the `route!` pattern grammar, shared by the
+//! `route_match` macros and by tools which read route definitions from source,
+//! such as `openapi_tools`.
+//!
+//! Everything here is built on `syn`, so a block can be parsed from the tokens
+//! of a `route!` invocation found in a source file:
+//!
+//! ```
+//! use route_match_syntax::match_stmnt::MatchArm;
+//! use route_match_syntax::match_stmnt::MatchStmnt;
+//!
+//! let stmnt: MatchStmnt = syn::parse_str(
+//!     "match (method, path) { GET /user/:id => get_user(id), _ => not_found() }",
+//! )
+//! .unwrap();
+//! let MatchArm::Route(route) = &stmnt.arms[0] else { panic!() };
+//! assert_eq!(route.path.template(), "/user/:id");
+//! ```
+
+pub mod header;
+pub mod host;
+pub mod match_stmnt;
+pub mod method;
+pub mod options;
+pub mod path;
+pub mod pattern;
+pub mod route;
+pub mod router;
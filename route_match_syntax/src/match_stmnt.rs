@@ -0,0 +1,252 @@
+use syn::braced;
+use syn::parenthesized;
+use syn::parse::Parse;
+use syn::spanned::Spanned;
+use syn::token::Paren;
+use syn::Attribute;
+use syn::Expr;
+use syn::Lit;
+use syn::Meta;
+use syn::Token;
+
+use crate::options::BlockOptions;
+use crate::pattern::PatternArm;
+use crate::route::Route;
+
+#[derive(Debug)]
+pub struct MatchStmnt {
+    pub options: BlockOptions,
+    pub match_token: Token![match],
+    pub arg: MatchArg,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(Debug)]
+pub enum MatchArg {
+    RequestProvider(Box<Expr>),
+    ComponentProviders {
+        method_provider: Box<Expr>,
+        path_provider: Box<Expr>,
+        host_provider: Option<Box<Expr>>,
+        headers_provider: Option<Box<Expr>>,
+    },
+}
+
+impl Parse for MatchArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(Paren) {
+            // If we're parsing a parenthetical arg,
+            // we parse the match arg within the parens
+            let content;
+            parenthesized!(content in input);
+            return content.parse();
+        }
+        // Like `match`, the arg can't be a struct literal, so `request { ... }`
+        // parses as the arg followed by the arms
+        let first = Expr::parse_without_eager_brace(input)?;
+        if input.peek(Token![,]) {
+            let _: Token![,] = input.parse()?;
+            let second = Expr::parse_without_eager_brace(input)?;
+            let mut third: Option<Box<Expr>> = None;
+            let mut fourth: Option<Box<Expr>> = None;
+            if input.peek(Token![,]) {
+                let _: Token![,] = input.parse()?;
+                if !input.is_empty() {
+                    third = Some(Box::new(Expr::parse_without_eager_brace(input)?));
+                }
+            }
+            if input.peek(Token![,]) {
+                let _: Token![,] = input.parse()?;
+                if !input.is_empty() {
+                    fourth = Some(Box::new(Expr::parse_without_eager_brace(input)?));
+                }
+            }
+            // `_` in place of the host, for matching headers without one
+            if let Some(Expr::Infer(_)) = third.as_deref() {
+                third = None;
+            }
+            return Ok(Self::ComponentProviders {
+                method_provider: Box::new(first),
+                path_provider: Box::new(second),
+                host_provider: third,
+                headers_provider: fourth,
+            });
+        }
+        Ok(Self::RequestProvider(Box::new(first)))
+    }
+}
+
+#[derive(Debug)]
+pub enum MatchArm {
+    Route(Route),
+    Pattern(PatternArm),
+    Default(Expr),
+}
+
+impl Parse for MatchArm {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let docs = parse_docs(input)?;
+        if input.peek(Token![use]) {
+            let pattern: PatternArm = input.parse()?;
+            if input.peek(Token![,]) {
+                let _: Token![,] = input.parse()?;
+            }
+            return Ok(Self::Pattern(pattern));
+        }
+        if input.peek(Token![_]) {
+            let forked_input = input.fork();
+            let _: Token![_] = forked_input.parse()?;
+            if forked_input.peek(Token![=>]) {
+                // parse Default arm
+                let _: Token![_] = input.parse()?;
+                let _: Token![=>] = input.parse()?;
+                let expr: Expr = input.parse()?;
+                if input.peek(Token![,]) {
+                    let _: Token![,] = input.parse()?;
+                }
+                return Ok(Self::Default(expr));
+            }
+        }
+        let mut route: Route = input.parse()?;
+        route.docs = docs;
+        if input.peek(Token![,]) {
+            let _: Token![,] = input.parse()?;
+        }
+        Ok(Self::Route(route))
+    }
+}
+
+/// Parses the doc comment before an arm, which is the only attribute arms accept
+fn parse_docs(input: syn::parse::ParseStream) -> syn::Result<Vec<String>> {
+    let mut docs = vec![];
+    for attr in input.call(Attribute::parse_outer)? {
+        let Meta::NameValue(doc) = &attr.meta else {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "only doc comments can be used on `route!` arms",
+            ));
+        };
+        if !doc.path.is_ident("doc") {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "only doc comments can be used on `route!` arms",
+            ));
+        }
+        if let Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(line),
+            ..
+        }) = &doc.value
+        {
+            docs.push(line.value());
+        }
+    }
+    Ok(docs)
+}
+
+impl Parse for MatchStmnt {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let options: BlockOptions = input.parse()?;
+        let match_token: Token![match] = input.parse()?;
+        let arg: MatchArg = input.parse()?;
+        let content;
+        let mut arms: Vec<MatchArm> = vec![];
+        braced!(content in input);
+        while !content.is_empty() {
+            let arm: MatchArm = content.parse()?;
+            if let Some(MatchArm::Default(default)) = arms.last() {
+                return Err(syn::Error::new(
+                    default.span(),
+                    "the default `_` arm must be the last arm",
+                ));
+            }
+            arms.push(arm);
+        }
+
+        Ok(Self {
+            options,
+            match_token,
+            arg,
+            arms,
+        })
+    }
+}
+
+impl MatchArg {
+    pub fn has_host(&self) -> bool {
+        matches!(
+            self,
+            MatchArg::RequestProvider(_)
+                | MatchArg::ComponentProviders {
+                    host_provider: Some(_),
+                    ..
+                }
+        )
+    }
+
+    pub fn has_headers(&self) -> bool {
+        matches!(
+            self,
+            MatchArg::RequestProvider(_)
+                | MatchArg::ComponentProviders {
+                    headers_provider: Some(_),
+                    ..
+                }
+        )
+    }
+}
+
+impl MatchStmnt {
+    /// Checks the arms are valid for the macro being expanded.  Without
+    /// `optional`, every request has to be handled, so a default arm is required.
+    pub fn validate(&self, optional: bool) -> syn::Result<()> {
+        let mut errors: Option<syn::Error> = None;
+        let mut report = |error: syn::Error| match &mut errors {
+            Some(errors) => errors.combine(error),
+            None => errors = Some(error),
+        };
+
+        for arm in &self.arms {
+            let MatchArm::Route(route) = arm else {
+                continue;
+            };
+            if let Err(error) = route.method.validate(&self.options.methods) {
+                report(error);
+            }
+            if let Err(error) = route.validate_captures() {
+                report(error);
+            }
+            if let Some(host) = &route.host {
+                if !self.arg.has_host() {
+                    report(syn::Error::new(
+                        host.span(),
+                        "host patterns require a host to match against, e.g. `match (method, path, host)`",
+                    ));
+                }
+            }
+            if let Some(header) = route.headers.first() {
+                if !self.arg.has_headers() {
+                    report(syn::Error::new(
+                        header.span,
+                        "header predicates require headers to match against, e.g. `match (method, path, host, headers)` or `match request`",
+                    ));
+                }
+            }
+        }
+
+        if !optional && !self.has_default() {
+            report(syn::Error::new(
+                self.match_token.span,
+                "`route!` requires a default `_ => ...` arm as the last arm; use `route_opt!` to return `None` instead",
+            ));
+        }
+
+        match errors {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+
+    pub fn has_default(&self) -> bool {
+        matches!(self.arms.last(), Some(MatchArm::Default(_)))
+    }
+}
@@ -0,0 +1,99 @@
+use proc_macro2::Span;
+use route_match_utils::methods;
+pub use route_match_utils::methods::STANDARD_METHODS;
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::Ident;
+use syn::LitStr;
+use syn::Token;
+
+#[derive(Debug)]
+pub enum Method {
+    Any(Span),
+    Named(Ident),
+    /// An extension method written as a string literal, e.g. `"PROPFIND"`
+    Extension(LitStr),
+    Param(Ident),
+}
+
+impl Parse for Method {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![_]) {
+            let token: Token![_] = input.parse()?;
+            return Ok(Method::Any(token.span));
+        } else if input.peek(Token![:]) {
+            let _: Token![:] = input.parse()?;
+            let ident = Ident::parse_any(input)?;
+            return Ok(Method::Param(ident));
+        } else if input.peek(LitStr) {
+            return Ok(Method::Extension(input.parse()?));
+        }
+        let ident: Ident = input.parse()?;
+        Ok(Method::Named(ident))
+    }
+}
+
+impl Method {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Any(span) => *span,
+            Self::Named(method) => method.span(),
+            Self::Extension(method) => method.span(),
+            Self::Param(method) => method.span(),
+        }
+    }
+
+    /// Rejects method names which are neither standard methods, nor declared
+    /// as extension methods with `#![methods(...)]`.  String literals declare
+    /// an extension method themselves, but are rejected if they look like a
+    /// typo of a known method.
+    pub fn validate(&self, extensions: &[Ident]) -> syn::Result<()> {
+        let declared: Vec<String> = extensions.iter().map(Ident::to_string).collect();
+        let declared: Vec<&str> = declared.iter().map(String::as_str).collect();
+        let ident = match self {
+            Method::Named(ident) => ident,
+            Method::Extension(method) => {
+                return methods::check_quoted(&method.value(), &declared)
+                    .map_err(|message| syn::Error::new(method.span(), message));
+            }
+            Method::Any(_) | Method::Param(_) => return Ok(()),
+        };
+        let name = ident.to_string();
+        if STANDARD_METHODS.contains(&name.as_str()) || declared.contains(&name.as_str()) {
+            return Ok(());
+        }
+
+        let message = match methods::suggestion(&name, &declared) {
+            Some(candidate) => format!(
+                "unknown HTTP method `{}`; did you mean `{}`?",
+                name, candidate
+            ),
+            None => format!(
+                "unknown HTTP method `{}`; declare extension methods with `#![methods({})]` before `match`",
+                name, name
+            ),
+        };
+        Err(syn::Error::new(ident.span(), message))
+    }
+
+    /// The method as it's written in the pattern, e.g. `GET`, `"PROPFIND"` or `_`
+    pub fn template(&self) -> String {
+        match self {
+            Method::Any(_) => "_".to_string(),
+            Method::Named(method) => method.to_string(),
+            Method::Extension(method) => format!("\"{}\"", method.value()),
+            Method::Param(param) => format!(":{}", param.unraw()),
+        }
+    }
+
+    /// The method as a pattern for the runtime `Router`, which has no
+    /// `#![methods(...)]`, so declared extension methods are quoted too
+    pub fn router_template(&self) -> String {
+        match self {
+            Method::Named(method) if !STANDARD_METHODS.contains(&method.to_string().as_str()) => {
+                format!("\"{}\"", method)
+            }
+            _ => self.template(),
+        }
+    }
+}
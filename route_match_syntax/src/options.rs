@@ -0,0 +1,65 @@
+use syn::parse::Parse;
+use syn::punctuated::Punctuated;
+use syn::Attribute;
+use syn::Expr;
+use syn::Ident;
+use syn::Meta;
+use syn::Token;
+
+/// Options for a `route!` block, given as inner attributes before `match`,
+/// e.g. `#![methods(PROPFIND, MKCOL)]`
+#[derive(Debug, Default)]
+pub struct BlockOptions {
+    /// Extension methods which can be named in the block's patterns
+    pub methods: Vec<Ident>,
+    pub order: Order,
+}
+
+/// The order in which arms are tried, set with `#![order = ...]`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// Arms are tried in the order they're written
+    #[default]
+    First,
+    /// Arms are tried from the most to the least specific pattern
+    Specific,
+}
+
+impl Parse for BlockOptions {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in input.call(Attribute::parse_inner)? {
+            if attr.path().is_ident("methods") {
+                let methods =
+                    attr.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?;
+                options.methods.extend(methods);
+            } else if attr.path().is_ident("order") {
+                options.order = Order::parse_meta(&attr.meta)?;
+            } else {
+                return Err(syn::Error::new_spanned(
+                    attr.path(),
+                    "unknown `route!` option; expected `#![methods(...)]` or `#![order = ...]`",
+                ));
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl Order {
+    fn parse_meta(meta: &Meta) -> syn::Result<Self> {
+        if let Meta::NameValue(name_value) = meta {
+            if let Expr::Path(path) = &name_value.value {
+                if path.path.is_ident("first") {
+                    return Ok(Order::First);
+                } else if path.path.is_ident("specific") {
+                    return Ok(Order::Specific);
+                }
+            }
+        }
+        Err(syn::Error::new_spanned(
+            meta,
+            "expected `#![order = first]` or `#![order = specific]`",
+        ))
+    }
+}
@@ -0,0 +1,222 @@
+use proc_macro2::Span;
+use syn::braced;
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::spanned::Spanned;
+use syn::token::Brace;
+use syn::token::Bracket;
+use syn::Ident;
+use syn::LitStr;
+use syn::Token;
+
+#[derive(Debug)]
+pub enum PathComponent {
+    Ident(Ident),
+    /// A segment written as a string literal, for text which isn't an
+    /// identifier, e.g. `/"user-profile"` or `/"2024"`
+    Quoted(LitStr),
+    Param(Ident),
+    Wildcard(Span),
+    Rest(Span, Option<Ident>),
+    Any(Span),
+    /// A final segment with one of a set of file extensions, e.g. `:id.{json,csv}`.
+    /// The matched extension is bound by an arm of the form `|ext| ...`.
+    Extension {
+        stem: Box<PathComponent>,
+        extensions: Vec<Ident>,
+        binding: Option<Ident>,
+    },
+}
+
+impl Parse for PathComponent {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let component = Self::parse_segment(input)?;
+        if !(input.peek(Token![.]) && !input.peek(Token![..]) && input.peek2(Brace)) {
+            return Ok(component);
+        }
+        if !matches!(
+            component,
+            PathComponent::Ident(_) | PathComponent::Quoted(_) | PathComponent::Param(_)
+        ) {
+            return Err(syn::Error::new(
+                component.span(),
+                "file extensions can only follow a name or a `:name` capture, e.g. `/report/:id.{json,csv}`",
+            ));
+        }
+
+        let _: Token![.] = input.parse()?;
+        let content;
+        braced!(content in input);
+        let extensions = content.parse_terminated(Ident::parse_any, Token![,])?;
+        if extensions.is_empty() {
+            return Err(syn::Error::new(
+                component.span(),
+                "expected at least one file extension",
+            ));
+        }
+        Ok(PathComponent::Extension {
+            stem: Box::new(component),
+            extensions: extensions.into_iter().collect(),
+            binding: None,
+        })
+    }
+}
+
+impl PathComponent {
+    fn parse_segment(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![:]) {
+            // this is a param
+            let _: Token![:] = input.parse()?;
+            let name = Ident::parse_any(input)?;
+            Ok(PathComponent::Param(name))
+        } else if input.peek(Token![_]) {
+            // this is a param
+            let token: Token![_] = input.parse()?;
+            Ok(PathComponent::Any(token.span))
+        } else if input.peek(Token![..]) {
+            let elipsis: Token![..] = input.parse()?;
+            if input.peek(Token![:]) {
+                let _: Token![:] = input.parse()?;
+                let name = Ident::parse_any(input)?;
+                let span = elipsis.span();
+                Ok(PathComponent::Rest(span, Some(name)))
+            } else {
+                let span = elipsis.span();
+                Ok(PathComponent::Rest(span, None))
+            }
+        } else if input.peek(Token![*]) {
+            let token: Token![*] = input.parse()?;
+            Ok(PathComponent::Wildcard(token.span))
+        } else if input.peek(LitStr) {
+            let segment: LitStr = input.parse()?;
+            let value = segment.value();
+            if value.is_empty() || value.contains('/') {
+                return Err(syn::Error::new(
+                    segment.span(),
+                    "a quoted segment must be non-empty and can't contain `/`",
+                ));
+            }
+            Ok(PathComponent::Quoted(segment))
+        } else {
+            let name: Ident = input.parse()?;
+            Ok(PathComponent::Ident(name))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Path {
+    pub components: Vec<PathComponent>,
+}
+
+impl Parse for Path {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut components: Vec<PathComponent> = vec![];
+
+        if input.peek(Token![_]) {
+            components.push(input.parse()?);
+            if Self::at_end(input) {
+                return Ok(Self { components });
+            }
+            let _: Token![=>] = input.parse()?;
+        }
+
+        loop {
+            if Self::at_end(input) {
+                break;
+            }
+
+            if input.peek(Token![..]) {
+                components.push(input.parse()?);
+                if Self::at_end(input) {
+                    break;
+                } else {
+                    // A "rest" token should always be the final one
+                    let _: Token![=>] = input.parse()?;
+                }
+            }
+            let _: Token![/] = input.parse()?;
+            // A trailing `/`, as in `/` itself, doesn't start another component
+            if Self::at_end(input) {
+                break;
+            }
+            let component: PathComponent = input.parse()?;
+            if let PathComponent::Extension { .. } = component {
+                if !Self::at_end(input) {
+                    return Err(syn::Error::new(
+                        component.span(),
+                        "file extensions can only be matched on the final segment",
+                    ));
+                }
+            }
+            components.push(component);
+        }
+
+        Ok(Self { components })
+    }
+}
+
+impl Path {
+    /// A path ends at the arm's `=>`, or at the `[...]` of its header predicates
+    fn at_end(input: syn::parse::ParseStream) -> bool {
+        input.is_empty() || input.peek(Token![=>]) || input.peek(Bracket)
+    }
+
+    pub fn span(&self) -> Span {
+        let Some(first) = self.components.first() else {
+            return Span::call_site();
+        };
+        let Some(last) = self.components.first() else {
+            return first.span();
+        };
+        first.span().join(last.span()).unwrap_or(first.span())
+    }
+
+    /// Renders the path as it was written in the pattern, e.g. `/user/:id`.
+    /// Raw captures are written without their `r#`, as the names they're
+    /// captured under.
+    pub fn template(&self) -> String {
+        if let Some(PathComponent::Any(_)) = self.components.first() {
+            return "_".to_string();
+        }
+        if self.components.is_empty() {
+            return "/".to_string();
+        }
+        self.components
+            .iter()
+            .map(|component| format!("/{}", component.template()))
+            .collect()
+    }
+}
+
+impl PathComponent {
+    pub fn template(&self) -> String {
+        match self {
+            PathComponent::Ident(ident) => ident.to_string(),
+            PathComponent::Quoted(segment) => segment.value(),
+            PathComponent::Param(param) => format!(":{}", param.unraw()),
+            PathComponent::Wildcard(_) => "*".to_string(),
+            PathComponent::Rest(_, Some(name)) => format!("..:{}", name.unraw()),
+            PathComponent::Rest(_, None) => "..".to_string(),
+            PathComponent::Any(_) => "_".to_string(),
+            PathComponent::Extension {
+                stem, extensions, ..
+            } => {
+                let extensions: Vec<String> = extensions.iter().map(Ident::to_string).collect();
+                format!("{}.{{{}}}", stem.template(), extensions.join(","))
+            }
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            PathComponent::Ident(ident) => ident.span(),
+            PathComponent::Quoted(segment) => segment.span(),
+            PathComponent::Param(param) => param.span(),
+            PathComponent::Wildcard(span) => *span,
+            PathComponent::Rest(span, _) => *span,
+            PathComponent::Any(span) => *span,
+            PathComponent::Extension { stem, .. } => stem.span(),
+        }
+    }
+}
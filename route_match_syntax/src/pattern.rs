@@ -0,0 +1,32 @@
+use syn::parse::Parse;
+use syn::Expr;
+use syn::Ident;
+use syn::Token;
+
+/// An arm which matches a shared `RoutePattern`: `use PATTERN => expr`, or
+/// `use PATTERN as captures => expr` to bind its captures
+#[derive(Debug)]
+pub struct PatternArm {
+    pub pattern: syn::Path,
+    pub binding: Option<Ident>,
+    pub expr: Expr,
+}
+
+impl Parse for PatternArm {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let _: Token![use] = input.parse()?;
+        let pattern: syn::Path = input.parse()?;
+        let mut binding = None;
+        if input.peek(Token![as]) {
+            let _: Token![as] = input.parse()?;
+            binding = Some(input.parse()?);
+        }
+        let _: Token![=>] = input.parse()?;
+        let expr: Expr = input.parse()?;
+        Ok(Self {
+            pattern,
+            binding,
+            expr,
+        })
+    }
+}
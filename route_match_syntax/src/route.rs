@@ -0,0 +1,241 @@
+use proc_macro2::Span;
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::parse_quote;
+use syn::spanned::Spanned;
+use syn::token::Bracket;
+use syn::Expr;
+use syn::Ident;
+use syn::Pat;
+use syn::PatIdent;
+use syn::Token;
+
+use crate::header::HeaderPredicate;
+use crate::host::Host;
+use crate::method::Method;
+use crate::path::Path;
+use crate::path::PathComponent;
+
+#[derive(Debug)]
+pub struct Route {
+    pub method: Method,
+    pub host: Option<Host>,
+    pub path: Path,
+    pub headers: Vec<HeaderPredicate>,
+    pub kind: ArmKind,
+    pub expr: Expr,
+    /// The arm's doc comment, one entry per line
+    pub docs: Vec<String>,
+}
+
+/// What a route does once its pattern has matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArmKind {
+    /// Evaluates the arm expression
+    Expr,
+    /// Calls the router given by the arm expression with the rest of the path,
+    /// continuing to the next arm if it returns `None`
+    Mount,
+    /// Evaluates an arm expression returning an `Option`, continuing to the
+    /// next arm if it's `None`
+    Try,
+}
+
+impl Parse for Route {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut route = Self::parse_pattern(input)?;
+        let _: Token![=>] = input.parse()?;
+
+        let mut kind = ArmKind::Expr;
+        if input.peek(Token![try]) {
+            let _: Token![try] = input.parse()?;
+            kind = ArmKind::Try;
+        } else if input.peek(Ident) && input.peek2(Ident) {
+            let forked_input = input.fork();
+            let keyword: Ident = forked_input.parse()?;
+            if keyword == "mount" {
+                let _: Ident = input.parse()?;
+                kind = ArmKind::Mount;
+                if !matches!(
+                    route.path.components.last(),
+                    Some(PathComponent::Rest(_, _))
+                ) {
+                    return Err(syn::Error::new(
+                        keyword.span(),
+                        "a mounted router requires a path ending in `..`, e.g. `_ /api/.. => mount api::route`",
+                    ));
+                }
+                route.validate_mount_prefix()?;
+            }
+        }
+
+        route.kind = kind;
+        route.expr = input.parse()?;
+        route.bind_extension();
+        Ok(route)
+    }
+}
+
+impl Route {
+    /// Parses the `METHOD host/path [headers]` pattern of a route, where the
+    /// host and headers are optional.  The arm expression is left as `()`.
+    pub fn parse_pattern(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let method: Method = input.parse()?;
+        let host = if Host::peek(input) {
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let path: Path = input.parse()?;
+        let headers = if input.peek(Bracket) {
+            HeaderPredicate::parse_list(input)?
+        } else {
+            vec![]
+        };
+        Ok(Route {
+            method,
+            host,
+            path,
+            headers,
+            kind: ArmKind::Expr,
+            expr: parse_quote!(()),
+            docs: vec![],
+        })
+    }
+
+    /// An arm of the form `|ext| ...` binds the extension matched by a
+    /// `.{json,csv}` segment, and the closure body becomes the arm expression
+    fn bind_extension(&mut self) {
+        let Some(PathComponent::Extension { binding, .. }) = self.path.components.last_mut() else {
+            return;
+        };
+        let Expr::Closure(closure) = &self.expr else {
+            return;
+        };
+        let [Pat::Ident(PatIdent { ident, .. })] = &closure.inputs.iter().collect::<Vec<_>>()[..]
+        else {
+            return;
+        };
+        *binding = Some(ident.clone());
+        self.expr = (*closure.body).clone();
+    }
+
+    pub fn span(&self) -> Span {
+        self.method
+            .span()
+            .join(self.expr.span())
+            .unwrap_or(self.expr.span())
+    }
+
+    pub fn has_indeterminate_length(&self) -> bool {
+        self.path.components.iter().fold(false, |acc, cmp| {
+            if let PathComponent::Wildcard(_) = cmp {
+                true
+            } else if let PathComponent::Rest(_, _) = cmp {
+                true
+            } else if let PathComponent::Any(_) = cmp {
+                true
+            } else {
+                acc
+            }
+        })
+    }
+
+    pub fn matches_any_path(&self) -> bool {
+        self.path.components.iter().fold(false, |acc, cmp| {
+            if let PathComponent::Any(_) = cmp {
+                true
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// The names bound by this route, in the order they are passed to the arm
+    pub fn captures(&self) -> Vec<Ident> {
+        let mut args: Vec<Ident> = vec![];
+
+        if let Some(host) = &self.host {
+            args.extend(host.captures());
+        }
+
+        let path_args = self.path.components.iter().filter_map(|component| {
+            if let PathComponent::Param(param) = component {
+                Some(vec![param.clone()])
+            } else if let PathComponent::Rest(_, Some(param)) = component {
+                Some(vec![param.clone()])
+            } else if let PathComponent::Extension { stem, binding, .. } = component {
+                let mut captures = vec![];
+                if let PathComponent::Param(param) = stem.as_ref() {
+                    captures.push(param.clone());
+                }
+                captures.extend(binding.clone());
+                Some(captures)
+            } else {
+                None
+            }
+        });
+        args.extend(path_args.flatten());
+
+        if let Method::Param(param) = &self.method {
+            args.push(param.clone());
+        }
+
+        args
+    }
+
+    /// A mounted router is only passed the method and the rest of the path, so
+    /// captures in its prefix would be silently dropped
+    fn validate_mount_prefix(&self) -> syn::Result<()> {
+        let mut errors: Option<syn::Error> = None;
+        for capture in self.captures() {
+            let error = syn::Error::new(
+                capture.span(),
+                format!(
+                    "`{}` can't be captured by a `mount` arm, since only the method and the rest of the path are passed to the mounted router; use `*` or `..` to match without capturing",
+                    capture.unraw()
+                ),
+            );
+            match &mut errors {
+                Some(errors) => errors.combine(error),
+                None => errors = Some(error),
+            }
+        }
+        errors.map_or(Ok(()), Err)
+    }
+
+    /// Checks every capture can be bound as a distinct variable in the arm
+    pub fn validate_captures(&self) -> syn::Result<()> {
+        let mut errors: Option<syn::Error> = None;
+        let mut report = |error: syn::Error| match &mut errors {
+            Some(errors) => errors.combine(error),
+            None => errors = Some(error),
+        };
+
+        // `r#type` and `type` bind the same name, so they're compared unraw
+        let captures = self.captures();
+        let names: Vec<Ident> = captures.iter().map(IdentExt::unraw).collect();
+        for (i, capture) in captures.iter().enumerate() {
+            let name = capture.to_string();
+            if names[..i].contains(&names[i]) {
+                report(syn::Error::new(
+                    capture.span(),
+                    format!("`{}` is captured more than once in this pattern", names[i]),
+                ));
+            } else if syn::parse_str::<Ident>(&name).is_err() {
+                report(syn::Error::new(
+                    capture.span(),
+                    format!(
+                        "`{}` is a keyword, and can't be used as a capture name; try `r#{}`",
+                        name, name
+                    ),
+                ));
+            }
+        }
+
+        match errors {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use syn::braced;
+use syn::parse::Parse;
+use syn::Attribute;
+use syn::Signature;
+use syn::Visibility;
+
+use crate::match_stmnt::MatchStmnt;
+
+/// The input to `router!`: a function whose body is a `route!` block
+#[derive(Debug)]
+pub struct RouterDef {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub sig: Signature,
+    pub stmnt: MatchStmnt,
+}
+
+impl Parse for RouterDef {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis: Visibility = input.parse()?;
+        let sig: Signature = input.parse()?;
+        let content;
+        braced!(content in input);
+        let stmnt: MatchStmnt = content.parse()?;
+        Ok(Self {
+            attrs,
+            vis,
+            sig,
+            stmnt,
+        })
+    }
+}
@@ -0,0 +1,55 @@
+//! Matching for the header predicates of a route, e.g. `[accept: application/json]`
+
+/// Checks the value of a header against the pattern of a header predicate.
+///
+/// - `accept` predicates match if the `Accept` header lists the pattern (see [`accepts`])
+/// - `content-type` predicates match the media type, ignoring its parameters,
+///   where the pattern may use `*` as a wildcard, e.g. `multipart/*`
+/// - Any other header matches if its value equals the pattern, ignoring case,
+///   or if it is present at all when the pattern is `*`
+pub fn matches(name: &str, value: Option<&str>, pattern: &str) -> bool {
+    let Some(value) = value else {
+        return false;
+    };
+    match name {
+        "accept" => accepts(value, pattern),
+        "content-type" => media_type_matches(media_type(value), pattern),
+        _ => pattern == "*" || value.trim().eq_ignore_ascii_case(pattern),
+    }
+}
+
+/// Checks whether an `Accept` header lists a media range matching `pattern`.
+///
+/// Ranges with `q=0` are rejected, and the catch-all `*/*` range is ignored,
+/// so that the first of several variants of a route doesn't match every
+/// request.  A variant without an `accept` predicate can serve as a fallback.
+pub fn accepts(accept: &str, pattern: &str) -> bool {
+    accept.split(',').any(|range| {
+        let mut params = range.split(';');
+        let media_range = params.next().unwrap_or_default().trim();
+        if media_range == "*/*" {
+            return false;
+        }
+        let rejected = params.any(|param| match param.split_once('=') {
+            Some((key, value)) => key.trim() == "q" && value.trim().parse::<f32>() == Ok(0.0),
+            None => false,
+        });
+        !rejected && media_type_matches(media_range, pattern)
+    })
+}
+
+/// The media type of a `Content-Type` value, without its parameters
+pub fn media_type(value: &str) -> &str {
+    value.split(';').next().unwrap_or_default().trim()
+}
+
+/// Compares two media types, either of which may use `*` as a wildcard type or subtype
+pub fn media_type_matches(a: &str, b: &str) -> bool {
+    let (a_type, a_subtype) = a.split_once('/').unwrap_or((a, ""));
+    let (b_type, b_subtype) = b.split_once('/').unwrap_or((b, ""));
+    part_matches(a_type, b_type) && part_matches(a_subtype, b_subtype)
+}
+
+fn part_matches(a: &str, b: &str) -> bool {
+    a == "*" || b == "*" || a.trim().eq_ignore_ascii_case(b.trim())
+}
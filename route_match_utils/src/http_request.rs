@@ -0,0 +1,85 @@
+//! Provider implementations for the `http` crate's request types, so a
+//! request can be matched directly with `match request { ... }`
+
+use http::request::Parts;
+use http::HeaderMap;
+use http::Request;
+use http::Uri;
+
+use crate::HeaderProvider;
+use crate::HostProvider;
+use crate::HttpMethodProvider;
+use crate::UrlPathProvider;
+
+impl<B> HttpMethodProvider for Request<B> {
+    fn method_str(&self) -> &str {
+        self.method().as_str()
+    }
+}
+
+impl<B> UrlPathProvider for Request<B> {
+    fn path_str(&self) -> &str {
+        self.uri().path()
+    }
+}
+
+/// The host of the URI, which is set for HTTP/2 requests and proxied
+/// requests, or else the `Host` header
+impl<B> HostProvider for Request<B> {
+    fn host_str(&self) -> Option<&str> {
+        request_host(self.uri(), self.headers())
+    }
+}
+
+impl<B> HeaderProvider for Request<B> {
+    fn header_str(&self, name: &str) -> Option<&str> {
+        self.headers().header_str(name)
+    }
+}
+
+impl HttpMethodProvider for Parts {
+    fn method_str(&self) -> &str {
+        self.method.as_str()
+    }
+}
+
+impl UrlPathProvider for Parts {
+    fn path_str(&self) -> &str {
+        self.uri.path()
+    }
+}
+
+impl HostProvider for Parts {
+    fn host_str(&self) -> Option<&str> {
+        request_host(&self.uri, &self.headers)
+    }
+}
+
+impl HeaderProvider for Parts {
+    fn header_str(&self, name: &str) -> Option<&str> {
+        self.headers.header_str(name)
+    }
+}
+
+impl UrlPathProvider for Uri {
+    fn path_str(&self) -> &str {
+        self.path()
+    }
+}
+
+impl HostProvider for Uri {
+    fn host_str(&self) -> Option<&str> {
+        self.host()
+    }
+}
+
+/// Headers whose values aren't visible ASCII are treated as missing
+impl HeaderProvider for HeaderMap {
+    fn header_str(&self, name: &str) -> Option<&str> {
+        self.get(name)?.to_str().ok()
+    }
+}
+
+fn request_host<'a>(uri: &'a Uri, headers: &'a HeaderMap) -> Option<&'a str> {
+    uri.host().or_else(|| headers.header_str("host"))
+}
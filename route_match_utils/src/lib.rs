@@ -1,3 +1,10 @@
+use std::collections::HashMap;
+
+pub mod headers;
+#[cfg(feature = "http")]
+mod http_request;
+pub mod methods;
+
 pub trait UrlPathProvider {
     fn path_str(&self) -> &str;
 }
@@ -6,6 +13,16 @@ pub trait HttpMethodProvider {
     fn method_str(&self) -> &str;
 }
 
+pub trait HostProvider {
+    fn host_str(&self) -> Option<&str>;
+}
+
+/// Provides request headers for header predicates, e.g. `[content-type: multipart/*]`.
+/// Header names are passed in lowercase, and should be looked up case-insensitively.
+pub trait HeaderProvider {
+    fn header_str(&self, name: &str) -> Option<&str>;
+}
+
 impl UrlPathProvider for &str {
     fn path_str(&self) -> &str {
         self
@@ -14,7 +31,7 @@ impl UrlPathProvider for &str {
 
 impl UrlPathProvider for String {
     fn path_str(&self) -> &str {
-        &self
+        self
     }
 }
 
@@ -26,6 +43,51 @@ impl HttpMethodProvider for &str {
 
 impl HttpMethodProvider for String {
     fn method_str(&self) -> &str {
-        &self
+        self
+    }
+}
+
+impl HostProvider for &str {
+    fn host_str(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl HostProvider for String {
+    fn host_str(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl HeaderProvider for [(&str, &str)] {
+    fn header_str(&self, name: &str) -> Option<&str> {
+        self.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+}
+
+impl HeaderProvider for Vec<(&str, &str)> {
+    fn header_str(&self, name: &str) -> Option<&str> {
+        self.as_slice().header_str(name)
+    }
+}
+
+impl HeaderProvider for Vec<(String, String)> {
+    fn header_str(&self, name: &str) -> Option<&str> {
+        self.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl HeaderProvider for HashMap<String, String> {
+    fn header_str(&self, name: &str) -> Option<&str> {
+        if let Some(value) = self.get(name) {
+            return Some(value);
+        }
+        self.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
     }
 }
@@ -0,0 +1,63 @@
+//! The method names a route pattern accepts, shared by `route!` and the
+//! runtime `Router` so the two reject the same methods
+
+/// The methods defined by RFC 9110, plus `PATCH`.  Each has a variant in `route_match::Method`.
+pub const STANDARD_METHODS: &[&str] = &[
+    "GET", "HEAD", "POST", "PUT", "DELETE", "CONNECT", "OPTIONS", "TRACE", "PATCH",
+];
+
+/// Whether a method name is an HTTP token, as defined in RFC 9110
+pub fn is_token(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+/// The standard or declared method an unknown method name is likely a typo
+/// of, e.g. `GET` for `GTE` or `get`
+pub fn suggestion<'a>(name: &str, declared: &[&'a str]) -> Option<&'a str> {
+    let name = name.to_ascii_uppercase();
+    STANDARD_METHODS
+        .iter()
+        .chain(declared)
+        .map(|candidate| (edit_distance(&name, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Checks an extension method written as a string literal, e.g. `"PROPFIND"`.
+/// Quoting a method declares it, but a name which is likely a typo of a
+/// standard or declared method is still rejected.
+pub fn check_quoted(name: &str, declared: &[&str]) -> Result<(), String> {
+    if STANDARD_METHODS.contains(&name) || declared.contains(&name) {
+        return Ok(());
+    }
+    if !is_token(name) {
+        return Err(format!("`\"{}\"` is not a valid HTTP method", name));
+    }
+    match suggestion(name, declared) {
+        Some(candidate) => Err(format!(
+            "unknown HTTP method `{}`; did you mean `{}`?",
+            name, candidate
+        )),
+        None => Ok(()),
+    }
+}
+
+/// The number of single character edits needed to turn `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a_char != *b_char);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,42 @@
+use route_match_utils::headers::accepts;
+use route_match_utils::headers::matches;
+use route_match_utils::headers::media_type_matches;
+
+#[test]
+fn test_media_type_matches() {
+    assert!(media_type_matches("application/json", "application/json"));
+    assert!(media_type_matches("Application/JSON", "application/json"));
+    assert!(media_type_matches("multipart/form-data", "multipart/*"));
+    assert!(media_type_matches("*/*", "text/html"));
+    assert!(!media_type_matches("text/html", "application/json"));
+    assert!(!media_type_matches("multipart/form-data", "text/*"));
+}
+
+#[test]
+fn test_accepts() {
+    let browser = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+    assert!(accepts(browser, "text/html"));
+    assert!(!accepts(browser, "application/json"));
+    assert!(accepts("application/json", "application/json"));
+    assert!(accepts("application/*", "application/json"));
+    assert!(!accepts("*/*", "application/json"));
+    assert!(!accepts("application/json;q=0", "application/json"));
+    assert!(accepts(
+        "text/plain; q=0.5, application/json",
+        "application/json"
+    ));
+}
+
+#[test]
+fn test_matches() {
+    let content_type = Some("multipart/form-data; boundary=abc");
+    assert!(matches("content-type", content_type, "multipart/*"));
+    assert!(matches("content-type", content_type, "multipart/form-data"));
+    assert!(!matches("content-type", content_type, "application/json"));
+    assert!(!matches("content-type", None, "multipart/*"));
+
+    assert!(matches("x-api-version", Some("2"), "2"));
+    assert!(matches("x-api-version", Some("2"), "*"));
+    assert!(!matches("x-api-version", Some("3"), "2"));
+    assert!(!matches("x-api-version", None, "*"));
+}
@@ -0,0 +1,197 @@
+//! Counts of the requests taken by each arm of every `route!` block, to find
+//! the routes a test suite never exercises.
+//!
+//! With the `coverage` feature enabled, each block counts the requests taken
+//! by its arms.  The counters are registered when the program is linked, so
+//! blocks which never run appear in the report with no hits.
+
+use std::fmt::Display;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use linkme::distributed_slice;
+
+/// The counters of every block in the program.  Not public API.
+#[doc(hidden)]
+#[distributed_slice]
+pub static BLOCKS: [&'static [Arm]];
+
+/// The counters for the arms of a `route!` block.  Not public API.
+#[doc(hidden)]
+pub struct Block<const N: usize> {
+    arms: [Arm; N],
+}
+
+impl<const N: usize> Block<N> {
+    pub const fn new(arms: [Arm; N]) -> Self {
+        Self { arms }
+    }
+
+    /// The counters to register in [`BLOCKS`]
+    pub const fn arms(&'static self) -> &'static [Arm] {
+        &self.arms
+    }
+
+    pub fn hit(&self, arm: usize) {
+        self.arms[arm].hits.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// The counter for an arm of a `route!` block.  Not public API.
+#[doc(hidden)]
+pub struct Arm {
+    method: &'static str,
+    host: Option<&'static str>,
+    path: &'static str,
+    file: &'static str,
+    line: u32,
+    hits: AtomicUsize,
+}
+
+impl Arm {
+    pub const fn new(
+        method: &'static str,
+        host: Option<&'static str>,
+        path: &'static str,
+        file: &'static str,
+        line: u32,
+    ) -> Self {
+        Self {
+            method,
+            host,
+            path,
+            file,
+            line,
+            hits: AtomicUsize::new(0),
+        }
+    }
+
+    /// The counter for the default arm, whose template is `_`
+    pub const fn default_arm(file: &'static str, line: u32) -> Self {
+        Self::new("_", None, "", file, line)
+    }
+
+    fn template(&self) -> String {
+        match (self.host, self.path) {
+            (_, "") => self.method.to_string(),
+            (Some(host), path) => format!("{} {}{}", self.method, host, path),
+            (None, path) => format!("{} {}", self.method, path),
+        }
+    }
+}
+
+/// The number of requests taken by an arm of a `route!` block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArmCoverage {
+    template: String,
+    file: &'static str,
+    line: u32,
+    hits: usize,
+}
+
+impl ArmCoverage {
+    /// The pattern of the arm, e.g. `GET /user/:id`, or `_` for the default arm
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// The source file of the arm
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// The line of the arm in its source file
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// The number of requests which took the arm.  A `try` or `mount` arm
+    /// only counts requests for which it returned `Some`, since routing
+    /// continues past it otherwise.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+}
+
+/// The coverage of every `route!` block, from [`report()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    arms: Vec<ArmCoverage>,
+}
+
+impl CoverageReport {
+    /// Every arm of every block, ordered by their location
+    pub fn arms(&self) -> &[ArmCoverage] {
+        &self.arms
+    }
+
+    /// The arms which no request has taken
+    pub fn unexercised(&self) -> impl Iterator<Item = &ArmCoverage> {
+        self.arms.iter().filter(|arm| arm.hits == 0)
+    }
+}
+
+/// Lists the arms which were never exercised, e.g.
+/// `src/api.rs:12: GET /user/:id`
+impl Display for CoverageReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unexercised: Vec<&ArmCoverage> = self.unexercised().collect();
+        write!(
+            f,
+            "{} of {} route arms were never exercised",
+            unexercised.len(),
+            self.arms.len()
+        )?;
+        for arm in unexercised {
+            write!(f, "\n  {}:{}: {}", arm.file, arm.line, arm.template)?;
+        }
+        Ok(())
+    }
+}
+
+/// The number of requests taken so far in this process by each arm of every
+/// `route!` block in the program.
+///
+/// ```
+/// use route_match::route;
+///
+/// fn api(method: &str, path: &str) -> u32 {
+///     route! {
+///         match (&method, &path) {
+///             GET /user/:id => 1,
+///             POST /user => 2,
+///             _ => 0,
+///         }
+///     }
+/// }
+///
+/// fn health(method: &str, path: &str) -> bool {
+///     route! {
+///         match (&method, &path) {
+///             GET /health => true,
+///             _ => false,
+///         }
+///     }
+/// }
+///
+/// api("GET", "/user/42");
+///
+/// // `health` never runs, but its arms are still reported
+/// let report = route_match::coverage::report();
+/// let unexercised: Vec<&str> = report.unexercised().map(|arm| arm.template()).collect();
+/// assert_eq!(unexercised, ["POST /user", "_", "GET /health", "_"]);
+/// ```
+pub fn report() -> CoverageReport {
+    let mut arms: Vec<ArmCoverage> = BLOCKS
+        .iter()
+        .flat_map(|arms| arms.iter())
+        .map(|arm| ArmCoverage {
+            template: arm.template(),
+            file: arm.file,
+            line: arm.line,
+            hits: arm.hits.load(Ordering::Relaxed),
+        })
+        .collect();
+    arms.sort_by(|a, b| (a.file, a.line).cmp(&(b.file, b.line)));
+    CoverageReport { arms }
+}
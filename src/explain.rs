@@ -0,0 +1,204 @@
+use std::fmt::Display;
+
+/// The first check a request failed to match an arm, as found by
+/// `route_explain!`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The request has a different method
+    Method {
+        expected: &'static str,
+        found: String,
+    },
+    /// The host has a different number of labels
+    HostLength { expected: usize, found: usize },
+    /// A label of the host differs from the pattern
+    HostLabel {
+        index: usize,
+        expected: &'static str,
+        found: String,
+    },
+    /// The path has a different number of segments, or fewer than `expected`
+    /// if `at_least` is set, as for patterns with a `..` segment
+    PathLength {
+        expected: usize,
+        at_least: bool,
+        found: usize,
+    },
+    /// A static segment, or a segment with file extensions, differs from the
+    /// pattern
+    Segment {
+        index: usize,
+        expected: &'static str,
+        found: String,
+    },
+    /// A header predicate isn't satisfied
+    Header {
+        name: &'static str,
+        expected: &'static str,
+        found: Option<String>,
+    },
+    /// The request doesn't match the shared pattern of a `use` arm
+    Pattern,
+    /// The pattern of a `try` arm matches, but its expression returned `None`
+    TryDeclined,
+    /// The pattern of a `mount` arm matches, but the mounted router returned
+    /// `None`
+    MountDeclined,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mismatch::Method { expected, found } => {
+                write!(f, "the method is `{}`, not `{}`", found, expected)
+            }
+            Mismatch::HostLength { expected, found } => {
+                write!(f, "the host has {} labels, not {}", found, expected)
+            }
+            Mismatch::HostLabel {
+                index,
+                expected,
+                found,
+            } => write!(f, "host label {} is `{}`, not `{}`", index, found, expected),
+            Mismatch::PathLength {
+                expected,
+                at_least: true,
+                found,
+            } => write!(
+                f,
+                "the path has {} segments, fewer than {}",
+                found, expected
+            ),
+            Mismatch::PathLength {
+                expected, found, ..
+            } => write!(f, "the path has {} segments, not {}", found, expected),
+            Mismatch::Segment {
+                index,
+                expected,
+                found,
+            } => write!(f, "segment {} is `{}`, not `{}`", index, found, expected),
+            Mismatch::Header {
+                name,
+                expected,
+                found: Some(found),
+            } => write!(
+                f,
+                "header `{}` is `{}`, which doesn't match `{}`",
+                name, found, expected
+            ),
+            Mismatch::Header { name, .. } => write!(f, "header `{}` is missing", name),
+            Mismatch::Pattern => f.write_str("the request doesn't match the pattern"),
+            Mismatch::TryDeclined => f.write_str("the `try` expression returned `None`"),
+            Mismatch::MountDeclined => f.write_str("the mounted router returned `None`"),
+        }
+    }
+}
+
+/// Whether a request matches an arm, and if not, why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArmExplanation {
+    pattern: String,
+    line: u32,
+    result: Result<(), Mismatch>,
+}
+
+impl ArmExplanation {
+    #[doc(hidden)]
+    pub fn new(pattern: String, line: u32, result: Result<(), Mismatch>) -> Self {
+        Self {
+            pattern,
+            line,
+            result,
+        }
+    }
+
+    /// The pattern of the arm, e.g. `GET /user/:id`, or `_` for the default arm
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// The line of the arm in its source file
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn is_match(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// The first check the request failed, if it doesn't match
+    pub fn mismatch(&self) -> Option<&Mismatch> {
+        self.result.as_ref().err()
+    }
+}
+
+impl Display for ArmExplanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: `{}`: ", self.line, self.pattern)?;
+        match &self.result {
+            Ok(()) => f.write_str("matches"),
+            Err(mismatch) => write!(f, "{}", mismatch),
+        }
+    }
+}
+
+/// How a request fares against each arm of a block, from `route_explain!`.
+///
+/// ```
+/// use route_match::{route_explain, Mismatch};
+///
+/// let explanation = route_explain! {
+///     match (&"GET", &"/users/42") {
+///         POST /user => (),
+///         GET /user/:id => (),
+///         _ => (),
+///     }
+/// };
+///
+/// let mismatches: Vec<Option<&Mismatch>> =
+///     explanation.arms().iter().map(|arm| arm.mismatch()).collect();
+/// assert_eq!(
+///     mismatches,
+///     [
+///         Some(&Mismatch::Method { expected: "POST", found: "GET".to_string() }),
+///         Some(&Mismatch::Segment { index: 0, expected: "user", found: "users".to_string() }),
+///         None,
+///     ]
+/// );
+/// assert_eq!(explanation.matched().unwrap().pattern(), "_");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    arms: Vec<ArmExplanation>,
+}
+
+impl Explanation {
+    #[doc(hidden)]
+    pub fn new(arms: Vec<ArmExplanation>) -> Self {
+        Self { arms }
+    }
+
+    /// Every arm of the block, in the order they're tried
+    pub fn arms(&self) -> &[ArmExplanation] {
+        &self.arms
+    }
+
+    /// The arm which would handle the request, if any
+    pub fn matched(&self) -> Option<&ArmExplanation> {
+        self.arms.iter().find(|arm| arm.is_match())
+    }
+}
+
+/// Lists each arm on its own line, e.g.
+/// ``line 12: `GET /user/:id`: segment 0 is `users`, not `user` ``
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, arm) in self.arms.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}", arm)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,388 @@
+//! Routing AWS Lambda events from API Gateway REST APIs (v1), HTTP APIs (v2)
+//! and Application Load Balancers.
+//!
+//! [`LambdaRequest`] deserializes any of the three payloads and implements the
+//! request provider traits, so an event can be matched directly:
+//!
+//! ```
+//! use route_match::lambda::LambdaRequest;
+//! use route_match::route;
+//!
+//! let request: LambdaRequest = serde_json::from_str(r#"{
+//!     "version": "2.0",
+//!     "routeKey": "$default",
+//!     "rawPath": "/prod/user/42",
+//!     "headers": { "host": "api.example.com" },
+//!     "requestContext": {
+//!         "stage": "prod",
+//!         "http": { "method": "GET", "path": "/prod/user/42" }
+//!     }
+//! }"#).unwrap();
+//!
+//! let response = route! {
+//!     match request {
+//!         GET /user/:id => format!("user {}", id),
+//!         _ => "not found".to_string(),
+//!     }
+//! };
+//! assert_eq!(response, "user 42");
+//! ```
+//!
+//! Requests from `lambda_http` are `http::Request`s, which the `http` feature
+//! already covers, except that their paths may start with the stage; wrap them
+//! in [`WithoutStage`] to route on the path without it.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::HeaderProvider;
+use crate::HostProvider;
+use crate::HttpMethodProvider;
+use crate::UrlPathProvider;
+
+/// The kind of payload a [`LambdaRequest`] was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// An API Gateway REST API event, payload format 1.0
+    ApiGatewayV1,
+    /// An API Gateway HTTP API event, payload format 2.0
+    ApiGatewayV2,
+    /// An Application Load Balancer event
+    Alb,
+}
+
+/// An API Gateway or ALB event, normalized for routing.
+///
+/// The path routed on is the raw request path relative to the API: when the
+/// event names the resource or route which matched, e.g. `/users/{id}` or
+/// `/{proxy+}`, the end of the raw path it matched is routed on, which leaves
+/// out custom domain base paths but keeps escapes like `%2F` as they were
+/// sent.  Otherwise the stage is stripped from the front of HTTP API paths,
+/// e.g. `/prod/users/42` is routed as `/users/42`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawEvent")]
+pub struct LambdaRequest {
+    kind: EventKind,
+    method: String,
+    path: String,
+    raw_path: String,
+    host: Option<String>,
+    headers: Vec<(String, String)>,
+    path_parameters: HashMap<String, String>,
+    stage: Option<String>,
+    body: Option<String>,
+    is_base64_encoded: bool,
+}
+
+impl LambdaRequest {
+    pub fn kind(&self) -> EventKind {
+        self.kind
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// The path routed on, relative to the API
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The path as received, which may include the stage or a base path
+    pub fn raw_path(&self) -> &str {
+        &self.raw_path
+    }
+
+    /// The `Host` header, or else the domain name of the request context
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// The headers with lowercase names, sorted by name.  Headers with several
+    /// values have them joined with `, `.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// The `pathParameters` of the event, extracted by API Gateway
+    pub fn path_parameters(&self) -> &HashMap<String, String> {
+        &self.path_parameters
+    }
+
+    /// The API Gateway stage, e.g. `prod` or `$default`
+    pub fn stage(&self) -> Option<&str> {
+        self.stage.as_deref()
+    }
+
+    /// The body, base64 encoded if [`is_base64_encoded`](Self::is_base64_encoded)
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    pub fn is_base64_encoded(&self) -> bool {
+        self.is_base64_encoded
+    }
+}
+
+impl HttpMethodProvider for LambdaRequest {
+    fn method_str(&self) -> &str {
+        &self.method
+    }
+}
+
+impl UrlPathProvider for LambdaRequest {
+    fn path_str(&self) -> &str {
+        &self.path
+    }
+}
+
+impl HostProvider for LambdaRequest {
+    fn host_str(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+}
+
+impl HeaderProvider for LambdaRequest {
+    fn header_str(&self, name: &str) -> Option<&str> {
+        self.headers.header_str(name)
+    }
+}
+
+/// A request whose path is routed without its leading stage, e.g. a
+/// `lambda_http` request for `/prod/users/42` is routed as `/users/42`.
+/// Paths which don't start with the stage, and the `$default` stage, are left
+/// as they are.
+///
+/// ```
+/// use route_match::lambda::WithoutStage;
+/// use route_match::UrlPathProvider;
+///
+/// let path = WithoutStage::new(&"/prod/users/42", "prod");
+/// assert_eq!(path.path_str(), "/users/42");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WithoutStage<'a, R: ?Sized> {
+    request: &'a R,
+    stage: &'a str,
+}
+
+impl<'a, R: ?Sized> WithoutStage<'a, R> {
+    pub fn new(request: &'a R, stage: &'a str) -> Self {
+        Self { request, stage }
+    }
+
+    pub fn request(&self) -> &'a R {
+        self.request
+    }
+}
+
+impl<R: HttpMethodProvider + ?Sized> HttpMethodProvider for WithoutStage<'_, R> {
+    fn method_str(&self) -> &str {
+        self.request.method_str()
+    }
+}
+
+impl<R: UrlPathProvider + ?Sized> UrlPathProvider for WithoutStage<'_, R> {
+    fn path_str(&self) -> &str {
+        strip_stage(self.request.path_str(), self.stage)
+    }
+}
+
+impl<R: HostProvider + ?Sized> HostProvider for WithoutStage<'_, R> {
+    fn host_str(&self) -> Option<&str> {
+        self.request.host_str()
+    }
+}
+
+impl<R: HeaderProvider + ?Sized> HeaderProvider for WithoutStage<'_, R> {
+    fn header_str(&self, name: &str) -> Option<&str> {
+        self.request.header_str(name)
+    }
+}
+
+/// The fields of all three payload formats, which are told apart by the
+/// request context
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEvent {
+    http_method: Option<String>,
+    path: Option<String>,
+    resource: Option<String>,
+    raw_path: Option<String>,
+    route_key: Option<String>,
+    headers: Option<HashMap<String, String>>,
+    multi_value_headers: Option<HashMap<String, Vec<String>>>,
+    path_parameters: Option<HashMap<String, String>>,
+    request_context: Option<RawContext>,
+    body: Option<String>,
+    #[serde(default)]
+    is_base64_encoded: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawContext {
+    stage: Option<String>,
+    domain_name: Option<String>,
+    http: Option<RawHttp>,
+    elb: Option<serde::de::IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+struct RawHttp {
+    method: String,
+}
+
+impl TryFrom<RawEvent> for LambdaRequest {
+    type Error = String;
+
+    fn try_from(event: RawEvent) -> Result<Self, String> {
+        let context = event.request_context;
+        let (stage, domain_name, http, elb) = match context {
+            Some(context) => (
+                context.stage,
+                context.domain_name,
+                context.http,
+                context.elb.is_some(),
+            ),
+            None => (None, None, None, false),
+        };
+        let path_parameters = event.path_parameters.unwrap_or_default();
+
+        let (kind, method, raw_path, template) = match http {
+            Some(http) if !elb => {
+                let raw_path = event.raw_path.ok_or("missing field `rawPath`")?;
+                let template = event
+                    .route_key
+                    .as_deref()
+                    .and_then(|key| key.split_once(' '))
+                    .map(|(_, template)| template.to_string());
+                (EventKind::ApiGatewayV2, http.method, raw_path, template)
+            }
+            _ => {
+                let kind = if elb {
+                    EventKind::Alb
+                } else {
+                    EventKind::ApiGatewayV1
+                };
+                let method = event.http_method.ok_or("missing field `httpMethod`")?;
+                let path = event.path.ok_or("missing field `path`")?;
+                (kind, method, path, event.resource)
+            }
+        };
+
+        let path = template
+            .and_then(|template| fill_template(&template, &path_parameters))
+            .and_then(|path| raw_suffix(&raw_path, &path))
+            .unwrap_or_else(|| match (kind, &stage) {
+                (EventKind::ApiGatewayV2, Some(stage)) => strip_stage(&raw_path, stage),
+                _ => &raw_path,
+            })
+            .to_string();
+
+        // `headers` only has the last value of a header which was sent more
+        // than once, so `multiValueHeaders` takes precedence
+        let mut headers: Vec<(String, String)> = event
+            .multi_value_headers
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, values)| (name.to_ascii_lowercase(), values.join(", ")))
+            .collect();
+        for (name, value) in event.headers.unwrap_or_default() {
+            let name = name.to_ascii_lowercase();
+            if !headers.iter().any(|(key, _)| *key == name) {
+                headers.push((name, value));
+            }
+        }
+        headers.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let host = headers
+            .header_str("host")
+            .map(str::to_string)
+            .or(domain_name);
+
+        Ok(Self {
+            kind,
+            method,
+            path,
+            raw_path,
+            host,
+            headers,
+            path_parameters,
+            stage,
+            body: event.body,
+            is_base64_encoded: event.is_base64_encoded,
+        })
+    }
+}
+
+/// Fills the parameters of an API Gateway resource or route template, e.g.
+/// `/users/{id}` or `/{proxy+}`, or `None` if one is missing
+fn fill_template(template: &str, parameters: &HashMap<String, String>) -> Option<String> {
+    let mut path = String::new();
+    for segment in template.split('/').filter(|segment| !segment.is_empty()) {
+        path.push('/');
+        match segment
+            .strip_prefix('{')
+            .and_then(|segment| segment.strip_suffix('}'))
+        {
+            Some(name) => path.push_str(parameters.get(name.trim_end_matches('+'))?),
+            None => path.push_str(segment),
+        }
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+    Some(path)
+}
+
+/// The end of the raw path which decodes to `path`, the path the API matched,
+/// e.g. `/files/a%2Fb` of `/api/files/a%2Fb` for `/files/a/b`
+fn raw_suffix<'a>(raw_path: &'a str, path: &str) -> Option<&'a str> {
+    if path == "/" {
+        return Some("/");
+    }
+    raw_path
+        .match_indices('/')
+        .map(|(start, _)| &raw_path[start..])
+        .find(|suffix| percent_decode(suffix) == path)
+}
+
+/// Decodes the `%XX` escapes of a path, leaving invalid ones as they are
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn strip_stage<'a>(path: &'a str, stage: &str) -> &'a str {
+    if stage.is_empty() || stage == "$default" {
+        return path;
+    }
+    match path
+        .strip_prefix('/')
+        .and_then(|rest| rest.strip_prefix(stage))
+    {
+        Some("") => "/",
+        Some(rest) if rest.starts_with('/') => rest,
+        _ => path,
+    }
+}
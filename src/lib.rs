@@ -1 +1,52 @@
+#[cfg(feature = "coverage")]
+pub mod coverage;
+mod explain;
+#[cfg(feature = "lambda")]
+pub mod lambda;
+mod method;
+mod mount;
+mod path_pattern;
+mod pattern;
+#[cfg(feature = "http")]
+pub mod response;
+mod route_info;
+mod router;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "tower")]
+pub mod tower;
+
+pub use explain::ArmExplanation;
+pub use explain::Explanation;
+pub use explain::Mismatch;
+pub use method::MatchMethod;
+pub use method::Method;
+pub use mount::Mount;
+pub use path_pattern::Params;
+pub use path_pattern::ParseError;
+pub use path_pattern::PathPattern;
+pub use pattern::Captures;
+pub use pattern::RoutePattern;
+pub use route_info::RouteInfo;
 pub use route_match_macros::route;
+pub use route_match_macros::route_explain;
+pub use route_match_macros::route_matches;
+pub use route_match_macros::route_opt;
+pub use route_match_macros::route_pattern;
+pub use route_match_macros::router;
+pub use route_match_utils::headers;
+pub use route_match_utils::HeaderProvider;
+pub use route_match_utils::HostProvider;
+pub use route_match_utils::HttpMethodProvider;
+pub use route_match_utils::UrlPathProvider;
+pub use router::RouteMatch;
+pub use router::Router;
+
+/// Re-exports used by the code generated by `route!`.  Not public API.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "coverage")]
+    pub use linkme;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+}
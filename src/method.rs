@@ -0,0 +1,134 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+
+/// The standard request methods which can be named in a route pattern.
+///
+/// `route!` compares the request method against these through
+/// [`MatchMethod`], so the method can be given as any type implementing it,
+/// such as `http::Method` with the `http` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Head,
+    Post,
+    Put,
+    Delete,
+    Connect,
+    Options,
+    Trace,
+    Patch,
+}
+
+impl Method {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Head => "HEAD",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+            Method::Connect => "CONNECT",
+            Method::Options => "OPTIONS",
+            Method::Trace => "TRACE",
+            Method::Patch => "PATCH",
+        }
+    }
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A request method which `route!` can match
+pub trait MatchMethod {
+    /// Whether this is the given standard method
+    fn is(&self, method: Method) -> bool;
+
+    /// Whether this is an extension method, written as a string literal in a
+    /// pattern, e.g. `"PROPFIND" /files/..`
+    fn is_named(&self, name: &str) -> bool {
+        self.as_str() == name
+    }
+
+    fn as_str(&self) -> &str;
+}
+
+impl<T: MatchMethod + ?Sized> MatchMethod for &T {
+    fn is(&self, method: Method) -> bool {
+        (**self).is(method)
+    }
+
+    fn as_str(&self) -> &str {
+        (**self).as_str()
+    }
+}
+
+impl MatchMethod for Method {
+    fn is(&self, method: Method) -> bool {
+        *self == method
+    }
+
+    fn as_str(&self) -> &str {
+        Method::as_str(self)
+    }
+}
+
+impl MatchMethod for str {
+    fn is(&self, method: Method) -> bool {
+        self == method.as_str()
+    }
+
+    fn as_str(&self) -> &str {
+        self
+    }
+}
+
+impl MatchMethod for String {
+    fn is(&self, method: Method) -> bool {
+        self == method.as_str()
+    }
+
+    fn as_str(&self) -> &str {
+        self
+    }
+}
+
+impl MatchMethod for Cow<'_, str> {
+    fn is(&self, method: Method) -> bool {
+        self == method.as_str()
+    }
+
+    fn as_str(&self) -> &str {
+        self
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<Method> for http::Method {
+    fn from(method: Method) -> Self {
+        match method {
+            Method::Get => http::Method::GET,
+            Method::Head => http::Method::HEAD,
+            Method::Post => http::Method::POST,
+            Method::Put => http::Method::PUT,
+            Method::Delete => http::Method::DELETE,
+            Method::Connect => http::Method::CONNECT,
+            Method::Options => http::Method::OPTIONS,
+            Method::Trace => http::Method::TRACE,
+            Method::Patch => http::Method::PATCH,
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl MatchMethod for http::Method {
+    fn is(&self, method: Method) -> bool {
+        *self == http::Method::from(method)
+    }
+
+    fn as_str(&self) -> &str {
+        http::Method::as_str(self)
+    }
+}
@@ -0,0 +1,43 @@
+use std::fmt::Display;
+use std::ops::Deref;
+
+/// The path handed to a router mounted with `_ /prefix/.. => mount router`.
+///
+/// A `Mount` dereferences to the remainder of the path following the mounted
+/// prefix, so it can be passed straight to another `route!` block, while the
+/// prefix itself is still available through [`Mount::prefix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mount<'a> {
+    prefix: &'a str,
+    path: &'a str,
+}
+
+impl<'a> Mount<'a> {
+    pub fn new(prefix: &'a str, path: &'a str) -> Self {
+        Self { prefix, path }
+    }
+
+    /// The part of the request path matched by the mounting arm, e.g. `/api/v1`
+    pub fn prefix(&self) -> &'a str {
+        self.prefix
+    }
+
+    /// The remainder of the request path, e.g. `users/42`
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+}
+
+impl Deref for Mount<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.path
+    }
+}
+
+impl Display for Mount<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.path)
+    }
+}
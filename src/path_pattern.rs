@@ -0,0 +1,374 @@
+use std::error::Error;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A path pattern parsed at runtime, for routes which can't be written in a
+/// `route!` block, such as those loaded from config files or plugins.
+///
+/// The syntax and matching rules are the same as the path part of a `route!`
+/// pattern, except that static segments aren't limited to Rust identifiers.
+/// Quoted segments, such as `/"user-profiles"`, are accepted too, so the
+/// templates in a `router!` table can be parsed.
+///
+/// ```
+/// use route_match::PathPattern;
+///
+/// let pattern = PathPattern::parse("/user/:id/..:rest").unwrap();
+///
+/// let params = pattern.captures("/user/42/posts/7").unwrap();
+/// assert_eq!(params.get("id"), Some("42"));
+/// assert_eq!(params.get("rest"), Some("posts/7"));
+/// assert!(!pattern.matches("/user"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPattern {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Static(String),
+    Param(String),
+    Wildcard,
+    Rest(Option<String>),
+    Any,
+    Extension {
+        stem: Box<Segment>,
+        extensions: Vec<String>,
+    },
+}
+
+impl PathPattern {
+    pub fn parse(pattern: &str) -> Result<Self, ParseError> {
+        let error = |offset: usize, message: String| ParseError::new(pattern, offset, message);
+
+        if pattern == "_" {
+            return Ok(Self {
+                segments: vec![Segment::Any],
+            });
+        }
+        if !pattern.starts_with('/') {
+            return Err(error(0, "expected a path starting with `/`, or `_`".into()));
+        }
+
+        let mut segments: Vec<Segment> = vec![];
+        let mut offset = 0;
+        for text in pattern.split('/') {
+            let start = offset;
+            offset += text.len() + 1;
+            if text.is_empty() {
+                continue;
+            }
+            if let Some(Segment::Rest(_) | Segment::Extension { .. }) = segments.last() {
+                return Err(error(
+                    start,
+                    "`..` and file extensions can only be matched on the final segment".into(),
+                ));
+            }
+            segments.push(Segment::parse(text).map_err(|message| error(start, message))?);
+        }
+
+        if segments.len() > 1 && segments.contains(&Segment::Any) {
+            return Err(error(
+                0,
+                "`_` matches every path, and can't be combined with other segments".into(),
+            ));
+        }
+        let pattern = Self { segments };
+        let names = pattern.capture_names();
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err(error(
+                    0,
+                    format!("`{}` is captured more than once in this pattern", name),
+                ));
+            }
+        }
+        Ok(pattern)
+    }
+
+    /// The names of the values captured by the pattern
+    pub fn capture_names(&self) -> Vec<&str> {
+        self.segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Param(name) | Segment::Rest(Some(name)) => Some(name.as_str()),
+                Segment::Extension { stem, .. } => match stem.as_ref() {
+                    Segment::Param(name) => Some(name.as_str()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        self.captures(path).is_some()
+    }
+
+    /// Returns the captured values if the path matches the pattern
+    pub fn captures<'p, 'a>(&'p self, path: &'a str) -> Option<Params<'p, 'a>> {
+        let mut params = Params {
+            names: vec![],
+            values: vec![],
+            extension: None,
+        };
+        if self.segments.first() == Some(&Segment::Any) {
+            return Some(params);
+        }
+
+        let components: Vec<&str> = path.split('/').filter(|comp| !comp.is_empty()).collect();
+        let indeterminate = self
+            .segments
+            .iter()
+            .any(|segment| matches!(segment, Segment::Wildcard | Segment::Rest(_)));
+        if indeterminate {
+            let min_count = self
+                .segments
+                .iter()
+                .filter(|segment| !matches!(segment, Segment::Rest(_)))
+                .count();
+            if components.len() < min_count {
+                return None;
+            }
+        } else if components.len() != self.segments.len() {
+            return None;
+        }
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Static(name) => {
+                    if components[i] != name {
+                        return None;
+                    }
+                }
+                Segment::Param(name) => params.push(name, components[i]),
+                Segment::Wildcard | Segment::Rest(None) | Segment::Any => {}
+                Segment::Rest(Some(name)) => params.push(name, &path[rest_offset(path, i)..]),
+                Segment::Extension { stem, extensions } => {
+                    let (stem_value, extension) = components[i].rsplit_once('.')?;
+                    if !extensions.iter().any(|ext| ext == extension) {
+                        return None;
+                    }
+                    match stem.as_ref() {
+                        Segment::Static(name) if stem_value != name => return None,
+                        Segment::Param(_) if stem_value.is_empty() => return None,
+                        Segment::Param(name) => params.push(name, stem_value),
+                        _ => {}
+                    }
+                    params.extension = Some(extension);
+                }
+            }
+        }
+        Some(params)
+    }
+}
+
+impl Segment {
+    fn parse(text: &str) -> Result<Self, String> {
+        if let Some((stem, extensions)) = text.split_once(".{") {
+            let Some(extensions) = extensions.strip_suffix('}') else {
+                return Err("expected file extensions to end with `}`".into());
+            };
+            let stem = Self::parse(stem)?;
+            if !matches!(stem, Segment::Static(_) | Segment::Param(_)) {
+                return Err(
+                    "file extensions can only follow a name or a `:name` capture, e.g. `/report/:id.{json,csv}`".into(),
+                );
+            }
+            let extensions: Vec<String> = extensions
+                .split(',')
+                .map(str::trim)
+                .filter(|extension| !extension.is_empty())
+                .map(str::to_string)
+                .collect();
+            if extensions.is_empty() {
+                return Err("expected at least one file extension".into());
+            }
+            if let Some(extension) = extensions.iter().find(|ext| !is_ident(ext)) {
+                return Err(format!("`{}` is not a valid file extension", extension));
+            }
+            return Ok(Segment::Extension {
+                stem: Box::new(stem),
+                extensions,
+            });
+        }
+
+        if let Some(text) = text
+            .strip_prefix('"')
+            .and_then(|text| text.strip_suffix('"'))
+            .filter(|text| !text.is_empty())
+        {
+            Ok(Segment::Static(text.to_string()))
+        } else if text == "_" {
+            Ok(Segment::Any)
+        } else if text == "*" {
+            Ok(Segment::Wildcard)
+        } else if text == ".." {
+            Ok(Segment::Rest(None))
+        } else if let Some(name) = text.strip_prefix("..:") {
+            Ok(Segment::Rest(Some(capture_name(name)?)))
+        } else if let Some(name) = text.strip_prefix(':') {
+            Ok(Segment::Param(capture_name(name)?))
+        } else if text.contains(['{', '}', ':', '*']) {
+            Err(format!("unexpected character in path segment `{}`", text))
+        } else {
+            Ok(Segment::Static(text.to_string()))
+        }
+    }
+}
+
+pub(crate) fn capture_name(name: &str) -> Result<String, String> {
+    if is_ident(name) && name != "_" {
+        Ok(name.to_string())
+    } else {
+        Err(format!("`{}` is not a valid capture name", name))
+    }
+}
+
+fn is_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Finds the byte offset in `path` where the non-empty segment at `index`
+/// starts, or the end of the path if there is no such segment
+fn rest_offset(path: &str, index: usize) -> usize {
+    let mut segment_count = 0;
+    let mut in_segment = false;
+    for (idx, c) in path.char_indices() {
+        if c == '/' {
+            in_segment = false;
+        } else if !in_segment {
+            if segment_count == index {
+                return idx;
+            }
+            in_segment = true;
+            segment_count += 1;
+        }
+    }
+    path.len()
+}
+
+impl FromStr for PathPattern {
+    type Err = ParseError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        Self::parse(pattern)
+    }
+}
+
+impl Display for PathPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.segments.first() == Some(&Segment::Any) {
+            return f.write_str("_");
+        }
+        if self.segments.is_empty() {
+            return f.write_str("/");
+        }
+        for segment in &self.segments {
+            write!(f, "/{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Segment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            // Quote segments which would otherwise parse as something else
+            Segment::Static(name)
+                if name.contains(['{', '}', ':', '*']) || ["_", ".."].contains(&name.as_str()) =>
+            {
+                write!(f, "\"{}\"", name)
+            }
+            Segment::Static(name) => f.write_str(name),
+            Segment::Param(name) => write!(f, ":{}", name),
+            Segment::Wildcard => f.write_str("*"),
+            Segment::Rest(Some(name)) => write!(f, "..:{}", name),
+            Segment::Rest(None) => f.write_str(".."),
+            Segment::Any => f.write_str("_"),
+            Segment::Extension { stem, extensions } => {
+                write!(f, "{}.{{{}}}", stem, extensions.join(","))
+            }
+        }
+    }
+}
+
+/// The values captured by a `PathPattern` or `Router`, by name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params<'p, 'a> {
+    names: Vec<&'p str>,
+    values: Vec<&'a str>,
+    extension: Option<&'a str>,
+}
+
+impl<'p, 'a> Params<'p, 'a> {
+    pub(crate) fn push(&mut self, name: &'p str, value: &'a str) {
+        self.names.push(name);
+        self.values.push(value);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        let index = self.names.iter().position(|n| *n == name)?;
+        self.values.get(index).copied()
+    }
+
+    /// The file extension matched by a pattern like `/report/:id.{json,csv}`
+    pub fn extension(&self) -> Option<&'a str> {
+        self.extension
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'p str, &'a str)> + '_ {
+        self.names.iter().copied().zip(self.values.iter().copied())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// An error parsing a runtime route pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pattern: String,
+    offset: usize,
+    message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(pattern: &str, offset: usize, message: impl Into<String>) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            offset,
+            message: message.into(),
+        }
+    }
+
+    /// The byte offset in the pattern where the error was found
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (at byte {} of `{}`)",
+            self.message, self.offset, self.pattern
+        )
+    }
+}
+
+impl Error for ParseError {}
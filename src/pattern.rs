@@ -0,0 +1,111 @@
+use std::fmt::Debug;
+use std::fmt::Display;
+
+type Matcher = for<'a> fn(&'a str, &'a str) -> Option<Vec<&'a str>>;
+
+/// A reusable route pattern, built with `route_pattern!`.
+///
+/// Patterns can be stored in a `const` or `static`, and shared between
+/// `route!` blocks (with a `use PATTERN => ...` arm) and any other code which
+/// needs to know whether a request matches a route, such as middleware.
+///
+/// ```
+/// use route_match::{route_pattern, RoutePattern};
+///
+/// const USER: RoutePattern = route_pattern!(GET /user/:id);
+///
+/// assert!(USER.matches("GET", "/user/42"));
+/// assert_eq!(USER.captures("GET", "/user/42").unwrap().get("id"), Some("42"));
+/// ```
+#[derive(Clone, Copy)]
+pub struct RoutePattern {
+    method: &'static str,
+    path: &'static str,
+    capture_names: &'static [&'static str],
+    matcher: Matcher,
+}
+
+impl RoutePattern {
+    #[doc(hidden)]
+    pub const fn new(
+        method: &'static str,
+        path: &'static str,
+        capture_names: &'static [&'static str],
+        matcher: Matcher,
+    ) -> Self {
+        Self {
+            method,
+            path,
+            capture_names,
+            matcher,
+        }
+    }
+
+    /// The method part of the pattern, e.g. `GET`, `"PROPFIND"`, `_` or `:method`
+    pub const fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The path part of the pattern, e.g. `/user/:id`
+    pub const fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// The names of the values captured by the pattern
+    pub const fn capture_names(&self) -> &'static [&'static str] {
+        self.capture_names
+    }
+
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        (self.matcher)(method, path).is_some()
+    }
+
+    /// Returns the captured values if the request matches the pattern
+    pub fn captures<'a>(&self, method: &'a str, path: &'a str) -> Option<Captures<'a>> {
+        let values = (self.matcher)(method, path)?;
+        Some(Captures {
+            names: self.capture_names,
+            values,
+        })
+    }
+}
+
+impl Display for RoutePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.path)
+    }
+}
+
+impl Debug for RoutePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RoutePattern")
+            .field(&format_args!("{}", self))
+            .finish()
+    }
+}
+
+/// The values captured by a `RoutePattern`, by name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captures<'a> {
+    names: &'static [&'static str],
+    values: Vec<&'a str>,
+}
+
+impl<'a> Captures<'a> {
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        let index = self.names.iter().position(|n| *n == name)?;
+        self.values.get(index).copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &'a str)> + '_ {
+        self.names.iter().copied().zip(self.values.iter().copied())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
@@ -0,0 +1,117 @@
+//! Responses for requests which no route handles, built as `http::Response`s.
+//!
+//! ```
+//! use route_match::response;
+//! use route_match::response::AllowedMethods;
+//! use route_match::router;
+//!
+//! router! {
+//!     fn route(method: &str, path: &str, allowed: &AllowedMethods) -> http::Response<String> {
+//!         match (&method, &path) {
+//!             GET /user/:id => http::Response::new(format!("user {}", id)),
+//!             DELETE /user/:id => http::Response::new(format!("deleted {}", id)),
+//!             _ => {
+//!                 let allowed = allowed.for_path(path);
+//!                 if allowed.is_empty() {
+//!                     response::not_found()
+//!                 } else if method == "OPTIONS" {
+//!                     response::options(&allowed)
+//!                 } else {
+//!                     response::method_not_allowed(&allowed)
+//!                 }
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let allowed = AllowedMethods::new(route::ROUTES)?;
+//! let response = route("POST", "/user/42", &allowed);
+//! assert_eq!(response.status(), http::StatusCode::METHOD_NOT_ALLOWED);
+//! assert_eq!(response.headers()[http::header::ALLOW], "GET, DELETE");
+//! assert_eq!(route("GET", "/posts", &allowed).status(), http::StatusCode::NOT_FOUND);
+//! # Ok::<(), route_match::ParseError>(())
+//! ```
+
+use http::header::ALLOW;
+use http::HeaderValue;
+use http::Response;
+use http::StatusCode;
+
+use crate::path_pattern::ParseError;
+use crate::path_pattern::PathPattern;
+use crate::route_info::RouteInfo;
+
+/// An empty `404 Not Found` response
+pub fn not_found<B: Default>() -> Response<B> {
+    status(StatusCode::NOT_FOUND)
+}
+
+/// An empty `405 Method Not Allowed` response, with an `Allow` header listing
+/// the methods the path can be requested with
+pub fn method_not_allowed<B: Default>(allowed: &[&str]) -> Response<B> {
+    with_allow(status(StatusCode::METHOD_NOT_ALLOWED), allowed)
+}
+
+/// An empty `204 No Content` response to an `OPTIONS` request, with an
+/// `Allow` header listing the methods the path can be requested with
+pub fn options<B: Default>(allowed: &[&str]) -> Response<B> {
+    with_allow(status(StatusCode::NO_CONTENT), allowed)
+}
+
+/// The methods named by the routes of a `router!` table, with their paths
+/// parsed once, to find the methods a path can be requested with for the
+/// `Allow` header
+#[derive(Debug, Clone)]
+pub struct AllowedMethods {
+    routes: Vec<(&'static str, PathPattern)>,
+}
+
+impl AllowedMethods {
+    /// Parses the paths of the routes which name a method.  Routes which match
+    /// any method or a host are left out.
+    pub fn new(routes: &[RouteInfo]) -> Result<Self, ParseError> {
+        let mut allowed = vec![];
+        for route in routes.iter().filter(|route| route.host().is_none()) {
+            let method = route.method();
+            if method == "_" || method.starts_with(':') {
+                continue;
+            }
+            let method = method
+                .strip_prefix('"')
+                .and_then(|method| method.strip_suffix('"'))
+                .unwrap_or(method);
+            allowed.push((method, PathPattern::parse(route.path())?));
+        }
+        Ok(Self { routes: allowed })
+    }
+
+    /// The methods of the routes whose paths match, in the order of their arms
+    pub fn for_path(&self, path: &str) -> Vec<&'static str> {
+        let mut allowed: Vec<&'static str> = vec![];
+        for (method, pattern) in &self.routes {
+            if pattern.matches(path) && !allowed.contains(method) {
+                allowed.push(method);
+            }
+        }
+        allowed
+    }
+}
+
+fn status<B: Default>(status: StatusCode) -> Response<B> {
+    let mut response = Response::new(B::default());
+    *response.status_mut() = status;
+    response
+}
+
+/// Methods which aren't valid method names are left out
+fn with_allow<B>(mut response: Response<B>, allowed: &[&str]) -> Response<B> {
+    let allowed: Vec<&str> = allowed
+        .iter()
+        .copied()
+        .filter(|method| http::Method::from_bytes(method.as_bytes()).is_ok())
+        .collect();
+    if let Ok(value) = HeaderValue::from_str(&allowed.join(", ")) {
+        response.headers_mut().insert(ALLOW, value);
+    }
+    response
+}
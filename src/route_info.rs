@@ -0,0 +1,111 @@
+use std::fmt::Display;
+
+use crate::pattern::RoutePattern;
+
+/// A route matched by a function defined with `router!`, as listed in the
+/// `ROUTES` table generated alongside it.
+///
+/// ```
+/// use route_match::router;
+///
+/// router! {
+///     fn route(method: &str, path: &str) -> &'static str {
+///         match (&method, &path) {
+///             GET /user/:id => "user",
+///             _ /files/..:path => "files",
+///             _ => "not found",
+///         }
+///     }
+/// }
+///
+/// let routes: Vec<String> = route::ROUTES.iter().map(|route| route.to_string()).collect();
+/// assert_eq!(routes, ["GET /user/:id", "_ /files/..:path"]);
+/// assert_eq!(route::ROUTES[1].capture_names(), ["path"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteInfo {
+    method: &'static str,
+    host: Option<&'static str>,
+    path: &'static str,
+    headers: &'static [&'static str],
+    capture_names: &'static [&'static str],
+    arm: usize,
+}
+
+impl RouteInfo {
+    #[doc(hidden)]
+    pub const fn new(
+        method: &'static str,
+        host: Option<&'static str>,
+        path: &'static str,
+        headers: &'static [&'static str],
+        capture_names: &'static [&'static str],
+        arm: usize,
+    ) -> Self {
+        Self {
+            method,
+            host,
+            path,
+            headers,
+            capture_names,
+            arm,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn from_pattern(pattern: &RoutePattern, arm: usize) -> Self {
+        Self::new(
+            pattern.method(),
+            None,
+            pattern.path(),
+            &[],
+            pattern.capture_names(),
+            arm,
+        )
+    }
+
+    /// The method part of the pattern, e.g. `GET`, `_` or `:method`.  Extension
+    /// methods are quoted, e.g. `"PROPFIND"`, as for [`Router`](crate::Router).
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The host part of the pattern, e.g. `:tenant.example.com`, if it has one
+    pub fn host(&self) -> Option<&'static str> {
+        self.host
+    }
+
+    /// The path part of the pattern, e.g. `/user/:id`
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// The header predicates of the pattern, e.g. `accept: application/json`
+    pub fn headers(&self) -> &'static [&'static str] {
+        self.headers
+    }
+
+    /// The names of the values captured by the pattern
+    pub fn capture_names(&self) -> &'static [&'static str] {
+        self.capture_names
+    }
+
+    /// The position the arm is written at in the block, counting from zero.
+    /// With `#![order = specific]` this can differ from its place in `ROUTES`.
+    pub fn arm(&self) -> usize {
+        self.arm
+    }
+}
+
+impl Display for RouteInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.host {
+            Some(host) => write!(f, "{} {}{}", self.method, host, self.path)?,
+            None => write!(f, "{} {}", self.method, self.path)?,
+        }
+        if !self.headers.is_empty() {
+            write!(f, " [{}]", self.headers.join(", "))?;
+        }
+        Ok(())
+    }
+}
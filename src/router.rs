@@ -0,0 +1,199 @@
+use std::fmt::Display;
+
+use route_match_utils::methods;
+use route_match_utils::methods::STANDARD_METHODS;
+
+use crate::path_pattern::capture_name;
+use crate::path_pattern::Params;
+use crate::path_pattern::ParseError;
+use crate::path_pattern::PathPattern;
+
+/// A router built at runtime from `METHOD /path` patterns, which behaves like
+/// a `route!` block: routes are tried in the order they're added, and the
+/// first one to match is used.
+///
+/// ```
+/// use route_match::Router;
+///
+/// let mut router = Router::new();
+/// router.add("GET /user/:id", "get user").unwrap();
+/// router.add(":method /user/..", "user fallback").unwrap();
+///
+/// let matched = router.route("GET", "/user/42").unwrap();
+/// assert_eq!(*matched.value(), "get user");
+/// assert_eq!(matched.params().get("id"), Some("42"));
+///
+/// let matched = router.route("DELETE", "/user/42").unwrap();
+/// assert_eq!(matched.params().get("method"), Some("DELETE"));
+/// assert!(router.route("GET", "/users").is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Router<T> {
+    routes: Vec<Route<T>>,
+}
+
+#[derive(Debug, Clone)]
+struct Route<T> {
+    method: MethodPattern,
+    path: PathPattern,
+    value: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MethodPattern {
+    Any,
+    Named(String),
+    Param(String),
+}
+
+impl<T> Router<T> {
+    pub fn new() -> Self {
+        Self { routes: vec![] }
+    }
+
+    /// Adds a route with a pattern like `GET /user/:id`, `_ /static/..` or
+    /// `:method /files/..:path`.  Methods are checked as in `route!`: an
+    /// unquoted method must be a standard one, and extension methods are
+    /// written as string literals, e.g. `"PROPFIND" /files/..`.
+    pub fn add(&mut self, pattern: &str, value: T) -> Result<&mut Self, ParseError> {
+        let pattern = pattern.trim();
+        let Some((method, path)) = pattern.split_once(char::is_whitespace) else {
+            return Err(ParseError::new(
+                pattern,
+                pattern.len(),
+                "expected a method followed by a path, e.g. `GET /user/:id`",
+            ));
+        };
+        let path_start = pattern.len() - path.trim_start().len();
+        let method =
+            MethodPattern::parse(method).map_err(|message| ParseError::new(pattern, 0, message))?;
+        let path = PathPattern::parse(path.trim_start()).map_err(|error| {
+            ParseError::new(pattern, path_start + error.offset(), error.message())
+        })?;
+        if let MethodPattern::Param(name) = &method {
+            if path.capture_names().contains(&name.as_str()) {
+                return Err(ParseError::new(
+                    pattern,
+                    0,
+                    format!("`{}` is captured more than once in this pattern", name),
+                ));
+            }
+        }
+
+        self.routes.push(Route {
+            method,
+            path,
+            value,
+        });
+        Ok(self)
+    }
+
+    /// Returns the first route which matches the request
+    pub fn route<'r, 'a>(
+        &'r self,
+        method: &'a str,
+        path: &'a str,
+    ) -> Option<RouteMatch<'r, 'a, T>> {
+        self.routes.iter().find_map(|route| {
+            let mut params = match &route.method {
+                MethodPattern::Named(name) if name != method => return None,
+                _ => route.path.captures(path)?,
+            };
+            if let MethodPattern::Param(name) = &route.method {
+                params.push(name, method);
+            }
+            Some(RouteMatch {
+                value: &route.value,
+                params,
+            })
+        })
+    }
+
+    /// The patterns of the routes, in the order they're tried, e.g. `GET /user/:id`
+    pub fn patterns(&self) -> impl Iterator<Item = String> + '_ {
+        self.routes
+            .iter()
+            .map(|route| format!("{} {}", route.method, route.path))
+    }
+
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+}
+
+impl<T> Default for Router<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MethodPattern {
+    fn parse(text: &str) -> Result<Self, String> {
+        if text == "_" {
+            return Ok(MethodPattern::Any);
+        }
+        if let Some(name) = text.strip_prefix(':') {
+            return Ok(MethodPattern::Param(capture_name(name)?));
+        }
+        if let Some(name) = text
+            .strip_prefix('"')
+            .and_then(|name| name.strip_suffix('"'))
+        {
+            methods::check_quoted(name, &[])?;
+            return Ok(MethodPattern::Named(name.to_string()));
+        }
+        if STANDARD_METHODS.contains(&text) {
+            return Ok(MethodPattern::Named(text.to_string()));
+        }
+        Err(match methods::suggestion(text, &[]) {
+            Some(candidate) => format!(
+                "unknown HTTP method `{}`; did you mean `{}`?",
+                text, candidate
+            ),
+            None if methods::is_token(text) => format!(
+                "unknown HTTP method `{}`; write extension methods as a string literal, e.g. `\"{}\"`",
+                text, text
+            ),
+            None => format!("`{}` is not a valid HTTP method", text),
+        })
+    }
+}
+
+impl Display for MethodPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MethodPattern::Any => f.write_str("_"),
+            MethodPattern::Named(name) if STANDARD_METHODS.contains(&name.as_str()) => {
+                f.write_str(name)
+            }
+            MethodPattern::Named(name) => write!(f, "\"{}\"", name),
+            MethodPattern::Param(name) => write!(f, ":{}", name),
+        }
+    }
+}
+
+/// A route matched by a `Router`, with the values captured by its pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteMatch<'r, 'a, T> {
+    value: &'r T,
+    params: Params<'r, 'a>,
+}
+
+impl<'r, 'a, T> RouteMatch<'r, 'a, T> {
+    /// The value the route was added with
+    pub fn value(&self) -> &'r T {
+        self.value
+    }
+
+    pub fn params(&self) -> &Params<'r, 'a> {
+        &self.params
+    }
+
+    pub fn into_params(self) -> Params<'r, 'a> {
+        self.params
+    }
+}
@@ -0,0 +1,140 @@
+//! Helpers for testing routers defined with `router!`.
+
+use std::cell::RefCell;
+use std::fmt::Debug;
+
+use crate::route_info::RouteInfo;
+
+/// Checks that a function defined with `router!` routes each request in a
+/// table to the expected value, reporting every case which doesn't rather
+/// than stopping at the first.
+///
+/// Each failure names the arm of the router which handled the request, from
+/// its `ROUTES` table, as recorded by the router while routing it.
+///
+/// ```
+/// use route_match::{assert_routes, router};
+///
+/// router! {
+///     fn route(method: &str, path: &str) -> Option<u32> {
+///         match (&method, &path) {
+///             GET /user/:id => 1,
+///             POST /user => 2,
+///         }
+///     }
+/// }
+///
+/// assert_routes!(route, {
+///     "GET /user/42" => Some(1),
+///     "POST /user" => Some(2),
+///     "DELETE /user/42" => None,
+/// });
+/// ```
+#[macro_export]
+macro_rules! assert_routes {
+    ($($router:ident)::+, { $($request:expr => $expected:expr),* $(,)? }) => {{
+        let mut cases = $crate::testing::RouteCases::new($($router)::+::ROUTES);
+        let router = $($router)::+;
+        $(
+            cases.check($request, $expected, |method, path| router(method, path));
+        )*
+        cases.finish();
+    }};
+}
+
+pub use crate::assert_routes;
+
+/// The `ROUTES` table of a router, and the index of the arm it took
+type Taken = (&'static [RouteInfo], usize);
+
+thread_local! {
+    static TAKEN: RefCell<Option<Vec<Taken>>> = const { RefCell::new(None) };
+}
+
+/// Called by the code `router!` generates when a request takes the arm at
+/// `index` of the table `routes`, while a case is being checked
+#[doc(hidden)]
+pub fn take_arm(routes: &'static [RouteInfo], index: usize) {
+    TAKEN.with(|taken| {
+        if let Some(taken) = taken.borrow_mut().as_mut() {
+            taken.push((routes, index));
+        }
+    });
+}
+
+/// The results of the cases checked by `assert_routes!`
+#[derive(Debug)]
+pub struct RouteCases {
+    routes: &'static [RouteInfo],
+    checked: usize,
+    failures: Vec<String>,
+}
+
+impl RouteCases {
+    /// Creates a set of cases for a router with the given `ROUTES` table
+    pub fn new(routes: &'static [RouteInfo]) -> Self {
+        Self {
+            routes,
+            checked: 0,
+            failures: vec![],
+        }
+    }
+
+    /// Routes a request like `GET /user/42`, and records a failure if the
+    /// value differs from the expected one
+    pub fn check<'a, T: PartialEq + Debug>(
+        &mut self,
+        request: &'a str,
+        expected: T,
+        route: impl FnOnce(&'a str, &'a str) -> T,
+    ) {
+        self.checked += 1;
+        let Some((method, path)) = request.trim().split_once(char::is_whitespace) else {
+            self.failures.push(format!(
+                "{}\n  expected a method followed by a path, e.g. `GET /user/42`",
+                request
+            ));
+            return;
+        };
+        let path = path.trim_start();
+        TAKEN.with(|taken| *taken.borrow_mut() = Some(vec![]));
+        let actual = route(method, path);
+        let taken = TAKEN.with(|taken| taken.borrow_mut().take().unwrap_or_default());
+        if actual == expected {
+            return;
+        }
+
+        // A router mounted by this one records its arm first, so the last
+        // entry for this table is the arm this router took
+        let arm = taken
+            .iter()
+            .rev()
+            .find(|(routes, _)| *routes == self.routes)
+            .map(|&(_, index)| index);
+        let handled = match arm {
+            Some(index) => match self.routes.get(index) {
+                Some(route) => format!("handled by arm {} `{}`", route.arm(), route),
+                None => "handled by the default arm".to_owned(),
+            },
+            None => "no arm handled the request".to_owned(),
+        };
+        self.failures.push(format!(
+            "{} {}\n  expected: {:?}\n     found: {:?}\n  {}",
+            method, path, expected, actual, handled
+        ));
+    }
+
+    /// Panics with a report of every failed case, if there are any
+    #[track_caller]
+    pub fn finish(self) {
+        if self.failures.is_empty() {
+            return;
+        }
+        panic!(
+            "{} of {} route cases failed:\n\n{}\n",
+            self.failures.len(),
+            self.checked,
+            self.failures.join("\n\n")
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Serving a `route!` router as a `tower::Service`, so it can be used with
+//! hyper-util, tonic-web, or as axum's `fallback_service`.
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use http::Request;
+use http::Response;
+use pin_project_lite::pin_project;
+use tower_service::Service;
+
+use crate::response;
+
+/// A `tower::Service` which routes each request with an async function,
+/// usually an `async move` block around `route!`.
+///
+/// The function may return a `Response`, or an `Option<Response>` as from
+/// `route_opt!`, in which case `None` is answered with an empty `404 Not
+/// Found`.  Errors should be turned into responses by the function, so the
+/// service never fails, as axum's `fallback_service` requires.
+///
+/// ```
+/// use route_match::route_opt;
+/// use route_match::tower::RouterService;
+///
+/// let service = RouterService::new(|request: http::Request<String>| async move {
+///     route_opt! {
+///         match &request {
+///             GET /user/:id => http::Response::new(format!("user {}", id)),
+///             POST /user => http::Response::new(format!("created {}", request.body())),
+///         }
+///     }
+/// });
+/// # let _ = service;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RouterService<F> {
+    router: F,
+}
+
+impl<F> RouterService<F> {
+    pub fn new(router: F) -> Self {
+        Self { router }
+    }
+}
+
+impl<F, Fut, B> Service<Request<B>> for RouterService<F>
+where
+    F: Fn(Request<B>) -> Fut,
+    Fut: Future,
+    Fut::Output: RouterResponse,
+{
+    type Response = Response<<Fut::Output as RouterResponse>::Body>;
+    type Error = Infallible;
+    type Future = ResponseFuture<Fut>;
+
+    /// Always ready, since routing doesn't hold any resources of its own
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        ResponseFuture {
+            future: (self.router)(request),
+        }
+    }
+}
+
+/// The result of a router function served by [`RouterService`]
+pub trait RouterResponse {
+    type Body;
+
+    fn into_response(self) -> Response<Self::Body>;
+}
+
+impl<B> RouterResponse for Response<B> {
+    type Body = B;
+
+    fn into_response(self) -> Response<B> {
+        self
+    }
+}
+
+/// `None` becomes an empty `404 Not Found`
+impl<B: Default> RouterResponse for Option<Response<B>> {
+    type Body = B;
+
+    fn into_response(self) -> Response<B> {
+        self.unwrap_or_else(response::not_found)
+    }
+}
+
+pin_project! {
+    /// The future returned by [`RouterService`]
+    #[derive(Debug)]
+    pub struct ResponseFuture<Fut> {
+        #[pin]
+        future: Fut,
+    }
+}
+
+impl<Fut> Future for ResponseFuture<Fut>
+where
+    Fut: Future,
+    Fut::Output: RouterResponse,
+{
+    type Output = Result<Response<<Fut::Output as RouterResponse>::Body>, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project()
+            .future
+            .poll(cx)
+            .map(|response| Ok(response.into_response()))
+    }
+}
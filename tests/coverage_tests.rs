@@ -0,0 +1,69 @@
+#![cfg(feature = "coverage")]
+
+use route_match::coverage;
+use route_match::route;
+use route_match::route_pattern;
+use route_match::RoutePattern;
+
+const FILES: RoutePattern = route_pattern!(GET /files/..:path);
+
+fn api(method: &str, path: &str) -> u32 {
+    route! {
+        match (&method, &path) {
+            GET /user/:id => id.len() as u32,
+            POST /user => 2,
+            use FILES => 3,
+            _ /admin/..:rest => try (rest == "status").then_some(4),
+            _ => 0,
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn never_called(method: &str, path: &str) -> u32 {
+    route! {
+        match (&method, &path) {
+            GET /health => 1,
+            _ => 0,
+        }
+    }
+}
+
+#[test]
+fn test_coverage_report() {
+    assert!(coverage::report()
+        .arms()
+        .iter()
+        .filter(|arm| arm.file() == file!())
+        .all(|arm| arm.hits() == 0));
+
+    api("GET", "/user/1");
+    api("GET", "/user/2");
+    api("GET", "/files/a/b");
+    // The `try` arm declines `/admin/users`, which the default arm takes
+    api("GET", "/admin/users");
+    api("GET", "/admin/status");
+
+    let report = coverage::report();
+    let arms: Vec<(&str, u32, usize)> = report
+        .arms()
+        .iter()
+        .filter(|arm| arm.file() == file!())
+        .map(|arm| (arm.template(), arm.line(), arm.hits()))
+        .collect();
+    assert_eq!(
+        arms,
+        [
+            ("GET /user/:id", 13, 2),
+            ("POST /user", 14, 0),
+            ("GET /files/..:path", 15, 1),
+            ("_ /admin/..:rest", 16, 1),
+            ("_", 17, 1),
+            ("GET /health", 26, 0),
+            ("_", 27, 0),
+        ]
+    );
+    assert!(report
+        .to_string()
+        .contains(&format!("\n  {}:14: POST /user", file!())));
+}
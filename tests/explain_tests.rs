@@ -0,0 +1,177 @@
+use route_match::route_explain;
+use route_match::route_pattern;
+use route_match::Explanation;
+use route_match::Mismatch;
+use route_match::Mount;
+use route_match::RoutePattern;
+
+const FILES: RoutePattern = route_pattern!(GET /files/..:path);
+
+fn users(method: &str, path: Mount) -> Option<&'static str> {
+    route_match::route_opt! {
+        match (&method, &path) {
+            GET /users/:id => { let _ = id; "user" },
+        }
+    }
+}
+
+fn explain(method: &str, path: &str) -> Explanation {
+    route_explain! {
+        match (&method, &path) {
+            GET /user/:id => id,
+            GET /report/:id.{json,csv} => id,
+            _ /assets/.. => "assets",
+            GET /count/:n => try n.parse::<u32>().ok(),
+            _ /api/.. => mount users,
+            use FILES => "files",
+        }
+    }
+}
+
+fn mismatches(explanation: &Explanation) -> Vec<Option<Mismatch>> {
+    explanation
+        .arms()
+        .iter()
+        .map(|arm| arm.mismatch().cloned())
+        .collect()
+}
+
+#[test]
+fn test_explain_path() {
+    let explanation = explain("GET", "/report/42.txt");
+    assert_eq!(
+        mismatches(&explanation),
+        [
+            Some(Mismatch::Segment {
+                index: 0,
+                expected: "user",
+                found: "report".to_string(),
+            }),
+            Some(Mismatch::Segment {
+                index: 1,
+                expected: ":id.{json,csv}",
+                found: "42.txt".to_string(),
+            }),
+            Some(Mismatch::Segment {
+                index: 0,
+                expected: "assets",
+                found: "report".to_string(),
+            }),
+            Some(Mismatch::Segment {
+                index: 0,
+                expected: "count",
+                found: "report".to_string(),
+            }),
+            Some(Mismatch::Segment {
+                index: 0,
+                expected: "api",
+                found: "report".to_string(),
+            }),
+            Some(Mismatch::Pattern),
+        ]
+    );
+    assert_eq!(explanation.matched(), None);
+
+    let explanation = explain("POST", "/");
+    assert_eq!(
+        mismatches(&explanation)[..3],
+        [
+            Some(Mismatch::Method {
+                expected: "GET",
+                found: "POST".to_string(),
+            }),
+            Some(Mismatch::Method {
+                expected: "GET",
+                found: "POST".to_string(),
+            }),
+            Some(Mismatch::PathLength {
+                expected: 1,
+                at_least: true,
+                found: 0,
+            }),
+        ]
+    );
+
+    let explanation = explain("GET", "/user/42/posts");
+    assert_eq!(
+        explanation.arms()[0].mismatch(),
+        Some(&Mismatch::PathLength {
+            expected: 2,
+            at_least: false,
+            found: 3,
+        })
+    );
+    assert_eq!(
+        explanation.to_string().lines().next(),
+        Some("line 21: `GET /user/:id`: the path has 3 segments, not 2")
+    );
+}
+
+#[test]
+fn test_explain_delegating_arms() {
+    let explanation = explain("GET", "/count/many");
+    assert_eq!(
+        explanation.arms()[3].mismatch(),
+        Some(&Mismatch::TryDeclined)
+    );
+    let explanation = explain("GET", "/count/3");
+    assert_eq!(explanation.matched().unwrap().pattern(), "GET /count/:n");
+
+    let explanation = explain("GET", "/api/posts/1");
+    assert_eq!(
+        explanation.arms()[4].mismatch(),
+        Some(&Mismatch::MountDeclined)
+    );
+    let explanation = explain("GET", "/api/users/1");
+    assert_eq!(explanation.matched().unwrap().pattern(), "_ /api/..");
+
+    let explanation = explain("GET", "/files/a.txt");
+    assert_eq!(
+        explanation.matched().unwrap().pattern(),
+        "GET /files/..:path"
+    );
+}
+
+#[test]
+fn test_explain_host_and_headers() {
+    let host = "api.example.org";
+    let headers = [("accept", "text/html")];
+    let explanation = route_explain! {
+        match (&"GET", &"/users/1", &host, headers) {
+            GET api.example.com/users/:id => id,
+            GET example.com/users/:id => id,
+            GET /users/:id [accept: application/json] => id,
+            GET /users/:id [x-debug: *] => id,
+            _ => "none",
+        }
+    };
+    assert_eq!(
+        mismatches(&explanation),
+        [
+            Some(Mismatch::HostLabel {
+                index: 2,
+                expected: "com",
+                found: "org".to_string(),
+            }),
+            Some(Mismatch::HostLength {
+                expected: 2,
+                found: 3,
+            }),
+            Some(Mismatch::Header {
+                name: "accept",
+                expected: "application/json",
+                found: Some("text/html".to_string()),
+            }),
+            Some(Mismatch::Header {
+                name: "x-debug",
+                expected: "*",
+                found: None,
+            }),
+            None,
+        ]
+    );
+    assert_eq!(
+        explanation.arms()[2].mismatch().unwrap().to_string(),
+        "header `accept` is `text/html`, which doesn't match `application/json`"
+    );
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "http")]
+
+use http::header::ALLOW;
+use http::Request;
+use http::StatusCode;
+use route_match::response;
+use route_match::response::AllowedMethods;
+use route_match::route;
+use route_match::router;
+use route_match::RouteInfo;
+use route_match::UrlPathProvider;
+
+#[test]
+fn test_http_request() {
+    fn route<B>(request: &Request<B>) -> String {
+        route! {
+            match request {
+                GET api.example.com/users/:id => format!("api user {}", id),
+                GET /users/:id [accept: application/json] => format!("json user {}", id),
+                GET /users/:id => format!("user {}", id),
+                "PROPFIND" /files/..:path => format!("propfind {}", path),
+                _ => "not found".to_string(),
+            }
+        }
+    }
+
+    let request =
+        |method: &str, uri: &str| Request::builder().method(method).uri(uri).body(()).unwrap();
+    assert_eq!(
+        &route(&request("GET", "https://api.example.com/users/1")),
+        "api user 1"
+    );
+    assert_eq!(&route(&request("GET", "/users/1?page=2")), "user 1");
+    assert_eq!(&route(&request("PROPFIND", "/files/a/b")), "propfind a/b");
+    assert_eq!(&route(&request("POST", "/users/1")), "not found");
+
+    let request = Request::builder()
+        .uri("/users/2")
+        .header("Host", "api.example.com")
+        .body(())
+        .unwrap();
+    assert_eq!(&route(&request), "api user 2");
+
+    let request = Request::builder()
+        .uri("/users/3")
+        .header("Accept", "application/json")
+        .body(())
+        .unwrap();
+    assert_eq!(&route(&request), "json user 3");
+}
+
+#[test]
+fn test_http_parts() {
+    fn route(parts: &http::request::Parts) -> &'static str {
+        route! {
+            match parts {
+                POST /upload [content-type: "multipart/*"] => "multipart upload",
+                POST /upload => "upload",
+                _ => "not found",
+            }
+        }
+    }
+
+    let (parts, _) = Request::builder()
+        .method("POST")
+        .uri("/upload")
+        .header("Content-Type", "multipart/form-data; boundary=x")
+        .body(())
+        .unwrap()
+        .into_parts();
+    assert_eq!(route(&parts), "multipart upload");
+
+    let uri: http::Uri = "https://example.com/a/b?c=d".parse().unwrap();
+    assert_eq!(uri.path_str(), "/a/b");
+}
+
+#[test]
+fn test_responses() {
+    router! {
+        fn route(method: &str, path: &str) -> Option<&'static str> {
+            match (&method, &path) {
+                GET /user/:id => { let _ = id; "user" },
+                "PURGE" /user/:id => { let _ = id; "purge" },
+                PUT /"user-profiles"/:r#type => { let _ = r#type; "profile" },
+                GET /report/:id.{json,csv} => |format| { let _ = (id, format); "report" },
+                _ /files/.. => "files",
+            }
+        }
+    }
+
+    let allowed = AllowedMethods::new(route::ROUTES).unwrap();
+    assert_eq!(allowed.for_path("/user/1"), ["GET", "PURGE"]);
+    assert!(allowed.for_path("/files/a").is_empty());
+    assert_eq!(allowed.for_path("/user-profiles/admin"), ["PUT"]);
+    assert_eq!(allowed.for_path("/report/7.csv"), ["GET"]);
+
+    let options: http::Response<()> = response::options(&["GET", "PURGE"]);
+    assert_eq!(options.status(), StatusCode::NO_CONTENT);
+    assert_eq!(options.headers()[ALLOW], "GET, PURGE");
+
+    // A method which can't be written in the header is left out on its own
+    let not_allowed: http::Response<()> =
+        response::method_not_allowed(&["GET", "BAD METHOD", "PURGE"]);
+    assert_eq!(not_allowed.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(not_allowed.headers()[ALLOW], "GET, PURGE");
+
+    let error =
+        AllowedMethods::new(&[RouteInfo::new("GET", None, "user", &[], &[], 0)]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "expected a path starting with `/`, or `_` (at byte 0 of `user`)"
+    );
+
+    let not_found: http::Response<String> = response::not_found();
+    assert_eq!(not_found.status(), StatusCode::NOT_FOUND);
+    assert!(not_found.headers().get(ALLOW).is_none());
+}
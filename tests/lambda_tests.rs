@@ -0,0 +1,131 @@
+#![cfg(feature = "lambda")]
+
+use route_match::lambda::EventKind;
+use route_match::lambda::LambdaRequest;
+use route_match::lambda::WithoutStage;
+use route_match::route;
+use route_match::HeaderProvider;
+use route_match::UrlPathProvider;
+
+fn fixture(name: &str) -> LambdaRequest {
+    let path = format!(
+        "{}/tests/fixtures/lambda/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    let event = std::fs::read(&path).unwrap();
+    serde_json::from_slice(&event).unwrap()
+}
+
+fn route(request: &LambdaRequest) -> String {
+    route! {
+        match request {
+            GET /users/:id [accept: application/json] => format!("json user {}", id),
+            GET /users/:id => format!("user {}", id),
+            PUT /files/..:path => format!("put {}", path),
+            POST /orders/:id/items => format!("items of order {}", id),
+            DELETE shop.example.com/orders/:id => format!("delete order {}", id),
+            GET /health => "ok".to_string(),
+            _ => "not found".to_string(),
+        }
+    }
+}
+
+#[test]
+fn test_api_gateway_v1() {
+    let request = fixture("apigw_v1.json");
+    assert_eq!(request.kind(), EventKind::ApiGatewayV1);
+    assert_eq!(request.path(), "/users/42");
+    assert_eq!(request.stage(), Some("prod"));
+    assert_eq!(
+        request.host(),
+        Some("abc123.execute-api.us-east-1.amazonaws.com")
+    );
+    assert_eq!(request.header_str("x-forwarded-proto"), Some("https"));
+    assert_eq!(request.body(), None);
+    assert_eq!(&route(&request), "json user 42");
+}
+
+#[test]
+fn test_api_gateway_v1_base_path() {
+    let request = fixture("apigw_v1_base_path.json");
+    assert_eq!(request.raw_path(), "/api/files/docs/readme.md");
+    assert_eq!(request.path(), "/files/docs/readme.md");
+    assert_eq!(
+        request.path_parameters().get("proxy").map(String::as_str),
+        Some("files/docs/readme.md")
+    );
+    assert_eq!(request.body(), Some("IyBSZWFkbWUK"));
+    assert!(request.is_base64_encoded());
+    assert_eq!(&route(&request), "put docs/readme.md");
+}
+
+#[test]
+fn test_api_gateway_v1_encoded_path() {
+    let request = fixture("apigw_v1_encoded.json");
+    assert_eq!(request.path(), "/files/a%2Fb");
+    assert_eq!(&route(&request), "put a%2Fb");
+
+    // Every value of a repeated header is kept, and headers are sorted by name
+    assert_eq!(
+        request.headers(),
+        [
+            ("accept".to_string(), "text/html, text/plain".to_string()),
+            ("host".to_string(), "example.com".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_api_gateway_v2_stage() {
+    let request = fixture("apigw_v2.json");
+    assert_eq!(request.kind(), EventKind::ApiGatewayV2);
+    assert_eq!(request.method(), "POST");
+    assert_eq!(request.raw_path(), "/prod/orders/7/items");
+    assert_eq!(request.path(), "/orders/7/items");
+    assert_eq!(request.header_str("content-type"), Some("application/json"));
+    assert_eq!(&route(&request), "items of order 7");
+}
+
+#[test]
+fn test_api_gateway_v2_route_key() {
+    let request = fixture("apigw_v2_route.json");
+    assert_eq!(request.stage(), Some("$default"));
+    assert_eq!(request.raw_path(), "/v1/orders/7");
+    assert_eq!(request.path(), "/orders/7");
+    assert_eq!(&route(&request), "delete order 7");
+}
+
+#[test]
+fn test_alb() {
+    let request = fixture("alb.json");
+    assert_eq!(request.kind(), EventKind::Alb);
+    assert_eq!(request.stage(), None);
+    assert_eq!(
+        request.host(),
+        Some("lambda-alb-123578498.us-east-2.elb.amazonaws.com")
+    );
+    assert_eq!(
+        request.header_str("accept"),
+        Some("text/html, application/json")
+    );
+    assert_eq!(&route(&request), "ok");
+}
+
+#[test]
+fn test_missing_fields() {
+    let error = serde_json::from_str::<LambdaRequest>(r#"{"path": "/users/1"}"#).unwrap_err();
+    assert_eq!(error.to_string(), "missing field `httpMethod`");
+}
+
+#[test]
+fn test_without_stage() {
+    let path = |path: &'static str, stage: &'static str| {
+        WithoutStage::new(&path, stage).path_str().to_string()
+    };
+    assert_eq!(path("/prod/users/1", "prod"), "/users/1");
+    assert_eq!(path("/prod", "prod"), "/");
+    assert_eq!(path("/users/1", "prod"), "/users/1");
+    assert_eq!(path("/production/users/1", "prod"), "/production/users/1");
+    assert_eq!(path("/$default/users/1", "$default"), "/$default/users/1");
+}
@@ -118,3 +118,635 @@ fn test_any_path() {
     assert_eq!(&route("POST", "/bar"), "POST_any");
     assert_eq!(&route("PATCH", "/bar"), "PATCH");
 }
+
+#[test]
+fn test_param_only_paths() {
+    fn route(method: &str, path: &str) -> String {
+        route! {
+            match (&method, &path) {
+                GET /:id => id.to_string(),
+                GET /..:rest => format!("rest: {}", rest),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route("GET", "/foo"), "foo");
+    assert_eq!(&route("GET", "/foo/bar"), "rest: foo/bar");
+    assert_eq!(&route("GET", "foo/bar"), "rest: foo/bar");
+    assert_eq!(&route("GET", "/"), "rest: ");
+}
+
+#[test]
+fn test_rest_requires_prefix() {
+    fn route(method: &str, path: &str) -> String {
+        route! {
+            match (&method, &path) {
+                GET /foo/bar/..:rest => rest.to_string(),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route("GET", "/foo/bar"), "");
+    assert_eq!(&route("GET", "foo/bar/baz"), "baz");
+    assert_eq!(&route("GET", "/foo"), "none");
+    assert_eq!(&route("GET", "/"), "none");
+}
+
+#[test]
+fn test_mount() {
+    use route_match::Mount;
+
+    fn users(method: &str, path: Mount) -> Option<String> {
+        route! {
+            match (&method, &path) {
+                GET /users/:id => Some(format!("{} user {}", path.prefix(), id)),
+                _ => None,
+            }
+        }
+    }
+
+    fn route(method: &str, path: &str) -> String {
+        route! {
+            match (&method, &path) {
+                _ /api/v1/.. => mount users,
+                GET /api/v1/..:rest => format!("fallthrough: {}", rest),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route("GET", "/api/v1/users/42"), "/api/v1 user 42");
+    assert_eq!(&route("GET", "api/v1/users/42"), "api/v1 user 42");
+    assert_eq!(&route("GET", "/api/v1/posts/42"), "fallthrough: posts/42");
+    assert_eq!(&route("POST", "/api/v1/users/42"), "none");
+    assert_eq!(&route("GET", "/api"), "none");
+}
+
+#[test]
+fn test_route_opt() {
+    use route_match::route_opt;
+
+    fn users(method: &str, path: &str) -> Option<u8> {
+        route_opt! {
+            match (&method, &path) {
+                GET /users => 1,
+                GET /users/:id => id.parse().unwrap(),
+            }
+        }
+    }
+
+    fn posts(method: &str, path: &str) -> Option<u8> {
+        route_opt! {
+            match (&method, &path) {
+                GET /posts => 2,
+                _ => 0,
+            }
+        }
+    }
+
+    fn route(method: &str, path: &str) -> u8 {
+        users(method, path).or_else(|| posts(method, path)).unwrap()
+    }
+
+    assert_eq!(users("GET", "/users"), Some(1));
+    assert_eq!(users("GET", "/users/42"), Some(42));
+    assert_eq!(users("POST", "/users"), None);
+    assert_eq!(users("GET", "/posts"), None);
+    assert_eq!(posts("GET", "/users"), Some(0));
+    assert_eq!(route("GET", "/users/7"), 7);
+    assert_eq!(route("GET", "/posts"), 2);
+}
+
+#[test]
+fn test_route_matches() {
+    use route_match::route_matches;
+
+    fn is_user(method: &str, path: &str) -> bool {
+        route_matches!(&method, &path, GET /user/:id)
+    }
+
+    fn user_id(method: &str, path: &str) -> Option<u32> {
+        route_matches!(&method, &path, GET /user/:id => id.parse().unwrap())
+    }
+
+    assert!(is_user("GET", "/user/42"));
+    assert!(!is_user("POST", "/user/42"));
+    assert!(!is_user("GET", "/user"));
+    assert_eq!(user_id("GET", "/user/42"), Some(42));
+    assert_eq!(user_id("GET", "/users"), None);
+
+    let (method, path) = ("PUT", "/files/a/b");
+    if let Some((method, rest)) =
+        route_matches!(&method, &path, :method /files/..:rest => (method, rest))
+    {
+        assert_eq!(*method, "PUT");
+        assert_eq!(rest, "a/b");
+    } else {
+        panic!("expected a match");
+    }
+}
+
+#[test]
+fn test_route_pattern() {
+    use route_match::route_pattern;
+    use route_match::RoutePattern;
+
+    const USER: RoutePattern = route_pattern!(GET /user/:id);
+    static PROTECTED: &[RoutePattern] = &[
+        route_pattern!(_ / admin / ..),
+        route_pattern!(:method /files/:name/..:rest),
+    ];
+
+    assert_eq!(USER.to_string(), "GET /user/:id");
+    assert!(USER.matches("GET", "/user/42"));
+    assert!(!USER.matches("GET", "/user/42/posts"));
+    assert_eq!(
+        USER.captures("GET", "/user/42").unwrap().get("id"),
+        Some("42")
+    );
+
+    let is_protected = |method: &str, path: &str| PROTECTED.iter().any(|p| p.matches(method, path));
+    assert!(is_protected("DELETE", "/admin/users"));
+    assert!(!is_protected("GET", "/user/42"));
+
+    let captures = PROTECTED[1].captures("PUT", "/files/a.txt/b/c").unwrap();
+    assert_eq!(
+        captures.iter().collect::<Vec<_>>(),
+        vec![("name", "a.txt"), ("rest", "b/c"), ("method", "PUT")]
+    );
+
+    fn route(method: &str, path: &str) -> String {
+        route! {
+            match (&method, &path) {
+                use USER as captures => format!("user {}", captures.get("id").unwrap()),
+                use PROTECTED_ADMIN => "admin".to_string(),
+                _ => "none".to_string(),
+            }
+        }
+    }
+    const PROTECTED_ADMIN: RoutePattern = route_pattern!(_ / admin / ..);
+
+    assert_eq!(&route("GET", "/user/42"), "user 42");
+    assert_eq!(&route("POST", "/admin"), "admin");
+    assert_eq!(&route("POST", "/user/42"), "none");
+}
+
+#[test]
+fn test_host() {
+    fn route(method: &str, host: &str, path: &str) -> String {
+        route! {
+            match (&method, &path, &host) {
+                GET api.example.com/users/:id => format!("api user {}", id),
+                GET :tenant.example.com/.. => format!("tenant {}", tenant),
+                GET _.my-site.static.net/..:rest => format!("static {}", rest),
+                GET /health => "health".to_string(),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route("GET", "api.example.com", "/users/42"), "api user 42");
+    assert_eq!(
+        &route("GET", "API.Example.com:8080", "/users/42"),
+        "api user 42"
+    );
+    assert_eq!(
+        &route("GET", "api.example.com.", "/users/42"),
+        "api user 42"
+    );
+    assert_eq!(
+        &route("GET", "acme.example.com", "/users/42"),
+        "tenant acme"
+    );
+    assert_eq!(&route("GET", "api.example.com", "/posts"), "tenant api");
+    assert_eq!(
+        &route("GET", "cdn.my-site.static.net", "/a/b"),
+        "static a/b"
+    );
+    assert_eq!(&route("GET", "a.b.example.com", "/posts"), "none");
+    assert_eq!(&route("GET", "example.org", "/health"), "health");
+}
+
+#[test]
+fn test_headers() {
+    fn route(method: &str, path: &str, headers: &[(&str, &str)]) -> &'static str {
+        route! {
+            match (&method, &path, _, headers) {
+                POST /upload [content-type: "multipart/*"] => "multipart upload",
+                POST /upload => "unsupported upload",
+                GET /users/:id [accept: application/json] => { let _ = id; "json" },
+                GET /users/:id [accept: text/html] => { let _ = id; "html" },
+                GET /users/:id => { let _ = id; "default" },
+                GET /feed [accept: application/vnd.api+json, x-api-version: 2] => "api feed",
+                GET /feed [x-debug: *] => "debug feed",
+                _ => "none",
+            }
+        }
+    }
+
+    assert_eq!(
+        route(
+            "POST",
+            "/upload",
+            &[("Content-Type", "multipart/form-data; boundary=x")]
+        ),
+        "multipart upload"
+    );
+    assert_eq!(
+        route("POST", "/upload", &[("content-type", "text/plain")]),
+        "unsupported upload"
+    );
+    assert_eq!(route("POST", "/upload", &[]), "unsupported upload");
+    assert_eq!(
+        route("GET", "/users/1", &[("Accept", "application/json")]),
+        "json"
+    );
+    assert_eq!(
+        route(
+            "GET",
+            "/users/1",
+            &[("Accept", "text/html;q=0.9, application/json;q=0")]
+        ),
+        "html"
+    );
+    assert_eq!(route("GET", "/users/1", &[("Accept", "*/*")]), "default");
+    assert_eq!(
+        route(
+            "GET",
+            "/feed",
+            &[
+                ("accept", "application/vnd.api+json"),
+                ("X-Api-Version", "2")
+            ]
+        ),
+        "api feed"
+    );
+    assert_eq!(
+        route("GET", "/feed", &[("accept", "application/vnd.api+json")]),
+        "none"
+    );
+    assert_eq!(route("GET", "/feed", &[("x-debug", "")]), "debug feed");
+}
+
+#[test]
+fn test_request_provider() {
+    struct Request {
+        method: String,
+        path: String,
+        host: Option<String>,
+        headers: Vec<(String, String)>,
+    }
+
+    impl route_match::HttpMethodProvider for Request {
+        fn method_str(&self) -> &str {
+            &self.method
+        }
+    }
+
+    impl route_match::UrlPathProvider for Request {
+        fn path_str(&self) -> &str {
+            &self.path
+        }
+    }
+
+    impl route_match::HostProvider for Request {
+        fn host_str(&self) -> Option<&str> {
+            self.host.as_deref()
+        }
+    }
+
+    impl route_match::HeaderProvider for Request {
+        fn header_str(&self, name: &str) -> Option<&str> {
+            self.headers.header_str(name)
+        }
+    }
+
+    fn route(request: &Request) -> String {
+        route! {
+            match request {
+                GET admin.example.com/.. => "admin".to_string(),
+                GET /users/:id [accept: application/json] => format!("json {}", id),
+                GET /users/:id => format!("user {}", id),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    let request = |method: &str, path: &str, host: Option<&str>, accept: Option<&str>| Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        host: host.map(str::to_string),
+        headers: accept
+            .map(|accept| vec![("Accept".to_string(), accept.to_string())])
+            .unwrap_or_default(),
+    };
+
+    assert_eq!(
+        &route(&request("GET", "/users/3", None, Some("application/json"))),
+        "json 3"
+    );
+    assert_eq!(&route(&request("GET", "/users/3", None, None)), "user 3");
+    assert_eq!(
+        &route(&request("GET", "/users/3", Some("admin.example.com"), None)),
+        "admin"
+    );
+    assert_eq!(&route(&request("POST", "/users/3", None, None)), "none");
+}
+
+#[test]
+fn test_extensions() {
+    fn route(method: &str, path: &str) -> String {
+        route! {
+            match (&method, &path) {
+                GET /report/:id.{json,csv,pdf} => |ext| format!("report {} as {}", id, ext),
+                GET /sitemap.{xml} => "sitemap".to_string(),
+                GET /files/:name.{txt} => format!("file {}", name),
+                GET /report/:id => format!("report {}", id),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route("GET", "/report/42.json"), "report 42 as json");
+    assert_eq!(&route("GET", "/report/42.pdf"), "report 42 as pdf");
+    assert_eq!(&route("GET", "/report/v1.2.csv"), "report v1.2 as csv");
+    assert_eq!(&route("GET", "/report/42.xml"), "report 42.xml");
+    assert_eq!(&route("GET", "/report/.json"), "report .json");
+    assert_eq!(&route("GET", "/sitemap.xml"), "sitemap");
+    assert_eq!(&route("GET", "/sitemap.json"), "none");
+    assert_eq!(&route("GET", "/files/notes.txt"), "file notes");
+    assert_eq!(&route("GET", "/files/notes.txt/more"), "none");
+}
+
+#[test]
+fn test_extension_methods() {
+    fn route(method: &str, path: &str) -> &'static str {
+        route! {
+            #![methods(PROPFIND, MKCOL)]
+            match (&method, &path) {
+                PROPFIND /files/.. => "propfind",
+                MKCOL /files/.. => "mkcol",
+                GET /files/.. => "get",
+                _ => "none",
+            }
+        }
+    }
+
+    assert_eq!(route("PROPFIND", "/files/a"), "propfind");
+    assert_eq!(route("MKCOL", "/files/a"), "mkcol");
+    assert_eq!(route("GET", "/files/a"), "get");
+    assert_eq!(route("COPY", "/files/a"), "none");
+}
+
+#[test]
+fn test_raw_identifier_capture() {
+    fn route(method: &str, path: &str) -> String {
+        route! {
+            match (&method, &path) {
+                GET /items/:r#type/:id => format!("{} {}", r#type, id),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route("GET", "/items/book/1"), "book 1");
+}
+
+#[test]
+fn test_quoted_segments() {
+    fn route(method: &str, path: &str) -> String {
+        route! {
+            match (&method, &path) {
+                GET /"user-profile"/:id => format!("profile {}", id),
+                GET /v1/"static"/"2024" => "static".to_string(),
+                GET /"data-export".{json,csv} => |ext| format!("export {}", ext),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route("GET", "/user-profile/7"), "profile 7");
+    assert_eq!(&route("GET", "/v1/static/2024"), "static");
+    assert_eq!(&route("GET", "/data-export.csv"), "export csv");
+    assert_eq!(&route("GET", "/user/7"), "none");
+    assert_eq!(&route("GET", "/data.csv"), "none");
+}
+
+#[test]
+fn test_hygiene() {
+    fn route(method: &str, path: &str) -> String {
+        let _path = "user path";
+        let _method = "user method";
+        let byte_offset = 7;
+        route! {
+            match (&method, &path) {
+                GET /files/:_path_str/..:byte_offset => format!("{} {}", _path_str, byte_offset),
+                GET /vars => format!("{}, {}, {}", _path, _method, byte_offset),
+                POST /:_path => {
+                    let _method = "shadowed";
+                    format!("{} {}", _path, _method)
+                }
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route("GET", "/files/a/b/c"), "a b/c");
+    assert_eq!(&route("GET", "/vars"), "user path, user method, 7");
+    assert_eq!(&route("POST", "/thing"), "thing shadowed");
+    assert_eq!(&route("POST", "/vars"), "vars shadowed");
+}
+
+#[test]
+fn test_try_arms() {
+    fn plugin_router(method: &str, rest: &str) -> Option<String> {
+        match (method, rest) {
+            ("GET", "plugin") => Some("plugin".to_string()),
+            _ => None,
+        }
+    }
+
+    fn route(method: &str, path: &str) -> String {
+        route! {
+            match (&method, &path) {
+                _ /legacy/..:rest => try plugin_router(method, rest),
+                GET /legacy/:page => format!("built-in {}", page),
+                GET /users/:id => try id.parse::<u32>().ok().map(|id| format!("user #{}", id)),
+                GET /users/:name => format!("user {}", name),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route("GET", "/legacy/plugin"), "plugin");
+    assert_eq!(&route("GET", "/legacy/about"), "built-in about");
+    assert_eq!(&route("GET", "/users/42"), "user #42");
+    assert_eq!(&route("GET", "/users/alice"), "user alice");
+    assert_eq!(&route("POST", "/legacy/plugin"), "none");
+
+    fn route_opt(method: &str, path: &str) -> Option<String> {
+        route_match::route_opt! {
+            match (&method, &path) {
+                _ /legacy/..:rest => try plugin_router(method, rest),
+            }
+        }
+    }
+
+    assert_eq!(
+        route_opt("GET", "/legacy/plugin").as_deref(),
+        Some("plugin")
+    );
+    assert_eq!(route_opt("GET", "/legacy/other"), None);
+}
+
+#[test]
+fn test_specific_order() {
+    fn route(method: &str, path: &str) -> String {
+        route! {
+            #![order = specific]
+            match (&method, &path) {
+                _ /files/.. => "any files".to_string(),
+                GET /files/..:rest => format!("files {}", rest),
+                GET /users/:id => format!("user {}", id),
+                GET /users/me => "me".to_string(),
+                _ /users/me => "any me".to_string(),
+                GET /users/:id/posts => format!("posts {}", id),
+                GET /:section/posts => format!("section {}", section),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route("GET", "/users/me"), "me");
+    assert_eq!(&route("POST", "/users/me"), "any me");
+    assert_eq!(&route("GET", "/users/42"), "user 42");
+    assert_eq!(&route("GET", "/users/42/posts"), "posts 42");
+    assert_eq!(&route("GET", "/blog/posts"), "section blog");
+    assert_eq!(&route("GET", "/files/a/b"), "files a/b");
+    assert_eq!(&route("PUT", "/files/a/b"), "any files");
+    assert_eq!(&route("GET", "/other"), "none");
+}
+
+const ADMIN: route_match::RoutePattern = route_match::route_pattern!(_ /admin/..:rest);
+
+route_match::router! {
+    /// Routes requests for the test API
+    fn api(method: &str, host: &str, path: &str) -> Option<String> {
+        match (&method, &path, &host) {
+            GET /users/me => "me".to_string(),
+            GET /users/:id => format!("user {}", id),
+            :verb :tenant.example.com/files/..:r#path => format!("{} {} {}", verb, tenant, path),
+            use ADMIN as captures => format!("admin {}", captures.get("rest").unwrap()),
+            _ /plugin/..:rest => try rest.strip_prefix("ok/").map(str::to_string),
+        }
+    }
+}
+
+#[test]
+fn test_router_routes() {
+    let routes: Vec<(String, usize, &[&str])> = api::ROUTES
+        .iter()
+        .map(|route| (route.to_string(), route.arm(), route.capture_names()))
+        .collect();
+    assert_eq!(
+        routes,
+        [
+            ("GET /users/me".to_string(), 0, &[][..]),
+            ("GET /users/:id".to_string(), 1, &["id"][..]),
+            (
+                ":verb :tenant.example.com/files/..:path".to_string(),
+                2,
+                &["tenant", "path", "verb"][..]
+            ),
+            ("_ /admin/..:rest".to_string(), 3, &["rest"][..]),
+            ("_ /plugin/..:rest".to_string(), 4, &["rest"][..]),
+        ]
+    );
+    assert_eq!(api::ROUTES[2].host(), Some(":tenant.example.com"));
+    assert_eq!(api::ROUTES[2].path(), "/files/..:path");
+
+    assert_eq!(
+        api("GET", "example.com", "/users/me").as_deref(),
+        Some("me")
+    );
+    assert_eq!(
+        api("GET", "example.com", "/users/7").as_deref(),
+        Some("user 7")
+    );
+    assert_eq!(
+        api("PUT", "acme.example.com", "/files/a/b").as_deref(),
+        Some("PUT acme a/b")
+    );
+    assert_eq!(
+        api("GET", "example.com", "/admin/users").as_deref(),
+        Some("admin users")
+    );
+    assert_eq!(
+        api("GET", "example.com", "/plugin/ok/x").as_deref(),
+        Some("x")
+    );
+    assert_eq!(api("GET", "example.com", "/plugin/x"), None);
+    assert_eq!(api("GET", "example.com", "/other"), None);
+}
+
+route_match::router! {
+    fn templates(method: &str, path: &str) -> Option<String> {
+        #![methods(MKCOL)]
+        match (&method, &path) {
+            GET /items/:r#type/:id => format!("{} {}", r#type, id),
+            "PROPFIND" /files/..:r#ref => r#ref.to_string(),
+            MKCOL /dirs/..:r#dir => r#dir.to_string(),
+            :r#verb /report/:id.{json,csv} => |ext| format!("{} {} {}", r#verb, id, ext),
+            _ /"user-profiles"/ * => "profile".to_string(),
+        }
+    }
+}
+
+#[test]
+fn test_router_templates_parse_at_runtime() {
+    for route in templates::ROUTES {
+        let pattern = route_match::PathPattern::parse(route.path())
+            .unwrap_or_else(|error| panic!("`{}` doesn't parse: {}", route, error));
+        for name in pattern.capture_names() {
+            assert!(
+                route.capture_names().contains(&name),
+                "`{}` captures `{}`, which isn't in {:?}",
+                route,
+                name,
+                route.capture_names()
+            );
+        }
+        let mut router = route_match::Router::new();
+        router
+            .add(&route.to_string(), ())
+            .unwrap_or_else(|error| panic!("`{}` doesn't parse: {}", route, error));
+    }
+    assert_eq!(templates::ROUTES[0].path(), "/items/:type/:id");
+    assert_eq!(templates::ROUTES[2].method(), "\"MKCOL\"");
+    assert_eq!(templates("GET", "/items/book/1").as_deref(), Some("book 1"));
+}
+
+#[test]
+fn test_router_in_function() {
+    const GREETING: &str = "hello";
+
+    route_match::router! {
+        fn local(method: &str, path: &str) -> Option<String> {
+            match (&method, &path) {
+                GET /greeting => GREETING.to_string(),
+                use ADMIN as captures => captures.get("rest").unwrap().to_string(),
+                use self::ADMIN => "unreachable".to_string(),
+            }
+        }
+    }
+
+    let routes: Vec<String> = local::ROUTES.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        routes,
+        ["GET /greeting", "_ /admin/..:rest", "_ /admin/..:rest"]
+    );
+    assert_eq!(local("GET", "/greeting").as_deref(), Some("hello"));
+    assert_eq!(local("GET", "/admin/users").as_deref(), Some("users"));
+}
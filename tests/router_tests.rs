@@ -0,0 +1,223 @@
+use route_match::route;
+use route_match::route_pattern;
+use route_match::PathPattern;
+use route_match::RoutePattern;
+use route_match::Router;
+
+const METHODS: &[&str] = &["GET", "POST", "DELETE", "PROPFIND"];
+
+const PATHS: &[&str] = &[
+    "",
+    "/",
+    "//",
+    "/user",
+    "/user/42",
+    "/user/42/",
+    "//user//42",
+    "/user/42/posts",
+    "/user/42/posts/7",
+    "/files",
+    "/files/",
+    "/files/a/b.txt",
+    "/files//a/",
+    "/assets",
+    "/assets/css/site.css",
+    "/a/b/c",
+    "/a/x/c",
+    "/a/x/c/d",
+    "/a/x/y",
+    "/report/7.json",
+    "/report/7.xml",
+    "/report/.json",
+    "/report/7.tar.csv",
+    "/feed.rss",
+    "/feed.atom",
+    "/feed.json",
+    "/feed",
+    "/x/1/2",
+    "/x/1",
+    "/café/ñ",
+];
+
+fn corpus() -> Vec<(RoutePattern, &'static str)> {
+    vec![
+        (route_pattern!(GET /), "GET /"),
+        (route_pattern!(GET /user/:id), "GET /user/:id"),
+        (route_pattern!(_ /user/:id/posts), "_ /user/:id/posts"),
+        (
+            route_pattern!(:method /files/..:path),
+            ":method /files/..:path",
+        ),
+        (route_pattern!(GET / assets / ..), "GET /assets/.."),
+        (route_pattern!(POST /a/ * /c), "POST /a/*/c"),
+        (route_pattern!(_ / a / b / c), "_ /a/b/c"),
+        (
+            route_pattern!(GET /report/:id.{json,csv}),
+            "GET /report/:id.{json,csv}",
+        ),
+        (route_pattern!(GET /feed.{rss,atom}), "GET /feed.{rss,atom}"),
+        (route_pattern!(DELETE /x/:a/:b), "DELETE /x/:a/:b"),
+        (route_pattern!("PROPFIND" /..:all), "\"PROPFIND\" /..:all"),
+        (route_pattern!(GET _), "GET _"),
+        (route_pattern!(:verb /user/..:rest), ":verb /user/..:rest"),
+    ]
+}
+
+#[test]
+fn test_runtime_patterns_match_macro_patterns() {
+    for (pattern, source) in corpus() {
+        let mut router = Router::new();
+        router.add(source, ()).unwrap();
+        for method in METHODS {
+            for path in PATHS {
+                let expected: Option<Vec<(&str, &str)>> = pattern
+                    .captures(method, path)
+                    .map(|captures| captures.iter().collect());
+                let actual: Option<Vec<(&str, &str)>> = router
+                    .route(method, path)
+                    .map(|matched| matched.params().iter().collect());
+                assert_eq!(
+                    actual, expected,
+                    "`{}` differs from the macro for {} {:?}",
+                    source, method, path
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_runtime_router_matches_route_block() {
+    fn route_block<'a>(method: &'a str, path: &'a str) -> (usize, Vec<&'a str>) {
+        route! {
+            match (&method, &path) {
+                GET /user/me => (0, vec![]),
+                GET /user/:id => (1, vec![id]),
+                _ /user/:id => (2, vec![id]),
+                POST /files/..:path => (3, vec![path]),
+                :method /files/ * => (4, vec![*method]),
+                GET /report/:id.{json,csv} => |ext| (5, vec![id, ext]),
+                _ /assets/.. => (6, vec![]),
+                GET _ => (7, vec![]),
+                _ => (8, vec![]),
+            }
+        }
+    }
+
+    let mut router = Router::new();
+    router
+        .add("GET /user/me", 0)
+        .unwrap()
+        .add("GET /user/:id", 1)
+        .unwrap()
+        .add("_ /user/:id", 2)
+        .unwrap()
+        .add("POST /files/..:path", 3)
+        .unwrap()
+        .add(":method /files/*", 4)
+        .unwrap()
+        .add("GET /report/:id.{json,csv}", 5)
+        .unwrap()
+        .add("_ /assets/..", 6)
+        .unwrap()
+        .add("GET _", 7)
+        .unwrap()
+        .add("_ _", 8)
+        .unwrap();
+
+    for method in METHODS {
+        for path in PATHS {
+            let actual = router.route(method, path).map(|matched| {
+                let mut values: Vec<&str> =
+                    matched.params().iter().map(|(_, value)| value).collect();
+                values.extend(matched.params().extension());
+                (*matched.value(), values)
+            });
+            assert_eq!(
+                actual,
+                Some(route_block(method, path)),
+                "the router differs from `route!` for {} {:?}",
+                method,
+                path
+            );
+        }
+    }
+}
+
+#[test]
+fn test_path_pattern_display() {
+    for pattern in [
+        "/",
+        "_",
+        "/user/:id",
+        "/files/..:path",
+        "/a/*/c",
+        "/report/:id.{json,csv}",
+        "/\"a:b\"/\"_\"",
+    ] {
+        assert_eq!(PathPattern::parse(pattern).unwrap().to_string(), pattern);
+    }
+    assert_eq!(
+        PathPattern::parse("//user//:id/").unwrap().to_string(),
+        "/user/:id"
+    );
+    let quoted = PathPattern::parse("/\"user-profiles\"/:id").unwrap();
+    assert_eq!(quoted.to_string(), "/user-profiles/:id");
+    assert!(quoted.matches("/user-profiles/42"));
+
+    let router: Router<()> = {
+        let mut router = Router::new();
+        router.add("GET /user/:id", ()).unwrap();
+        router.add("\"PROPFIND\" /files/..", ()).unwrap();
+        router
+    };
+    let patterns: Vec<String> = router.patterns().collect();
+    assert_eq!(patterns, ["GET /user/:id", "\"PROPFIND\" /files/.."]);
+}
+
+#[test]
+fn test_path_pattern_errors() {
+    let error = PathPattern::parse("user/:id").unwrap_err();
+    assert_eq!(error.offset(), 0);
+
+    let error = PathPattern::parse("/files/../:name").unwrap_err();
+    assert_eq!(error.offset(), 10);
+    assert_eq!(
+        error.message(),
+        "`..` and file extensions can only be matched on the final segment"
+    );
+
+    let error = PathPattern::parse("/user/:id/:id").unwrap_err();
+    assert_eq!(
+        error.message(),
+        "`id` is captured more than once in this pattern"
+    );
+
+    assert!(PathPattern::parse("/user/:").is_err());
+    assert!(PathPattern::parse("/user/_/posts").is_err());
+    assert!(PathPattern::parse("/report/:id.{}").is_err());
+    assert!(PathPattern::parse("/..:rest.{json}").is_err());
+
+    let mut router = Router::new();
+    assert!(router.add("/user", ()).is_err());
+    assert!(router.add("G(ET /user", ()).is_err());
+    let error = router.add("GTE /user", ()).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "unknown HTTP method `GTE`; did you mean `GET`?"
+    );
+    let error = router.add("\"get\" /user", ()).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "unknown HTTP method `get`; did you mean `GET`?"
+    );
+    let error = router.add("PROPFIND /files/..", ()).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "unknown HTTP method `PROPFIND`; write extension methods as a string literal, e.g. `\"PROPFIND\"`"
+    );
+    assert!(router.add(":id /user/:id", ()).is_err());
+    let error = router.add("GET /user/:1", ()).unwrap_err();
+    assert_eq!(error.offset(), 10);
+    assert!(router.is_empty());
+}
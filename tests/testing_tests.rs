@@ -0,0 +1,199 @@
+#![cfg(feature = "testing")]
+
+use route_match::assert_routes;
+use route_match::testing::RouteCases;
+
+mod api {
+    use route_match::router;
+
+    router! {
+        pub fn route<'a>(method: &'a str, path: &'a str) -> Option<&'a str> {
+            match (&method, &path) {
+                GET /user/:id => id,
+                POST /user => "create",
+                GET /"user-profiles" => "profiles",
+                _ /user/..:rest => rest,
+            }
+        }
+    }
+}
+
+mod conditional {
+    use route_match::router;
+
+    router! {
+        pub fn route(method: &str, path: &str, host: &str, headers: &[(&str, &str)]) -> Option<&'static str> {
+            match (&method, &path, &host, headers) {
+                GET admin.example.com/user/ * => "admin",
+                GET /user/ * [accept: application/json] => "json",
+                GET /user/ * => "user",
+                _ /user/.. => "rest",
+            }
+        }
+    }
+}
+
+mod specific {
+    use route_match::router;
+
+    router! {
+        pub fn route<'a>(method: &'a str, path: &'a str) -> &'a str {
+            #![order = specific]
+            match (&method, &path) {
+                GET /files/..:path => path,
+                GET /files/:name => name,
+                GET /files/index => "index",
+                _ => "not found",
+            }
+        }
+    }
+}
+
+mod delegating {
+    use route_match::router;
+
+    fn admin(path: &str) -> Option<&'static str> {
+        (path == "/admin/status").then_some("status")
+    }
+
+    router! {
+        pub fn route<'a>(method: &'a str, path: &'a str) -> &'a str {
+            match (&method, &path) {
+                _ /admin/.. => try admin(path),
+                GET /admin/users => "users",
+                _ => "not found",
+            }
+        }
+    }
+}
+
+#[test]
+fn test_assert_routes_passes() {
+    assert_routes!(api::route, {
+        "GET /user/42" => Some("42"),
+        "POST /user" => Some("create"),
+        "GET /user-profiles" => Some("profiles"),
+        "DELETE /user/42/posts" => Some("42/posts"),
+        "GET /posts" => None,
+    });
+}
+
+#[test]
+fn test_assert_routes_reports_each_failure() {
+    let panic = std::panic::catch_unwind(|| {
+        assert_routes!(api::route, {
+            "GET /user/42" => Some("43"),
+            "POST /user" => Some("create"),
+            "GET /posts" => Some("posts"),
+            "/user" => None,
+        });
+    })
+    .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert_eq!(
+        message,
+        "3 of 4 route cases failed:\n\
+         \n\
+         GET /user/42\n  \
+         expected: Some(\"43\")\n     \
+         found: Some(\"42\")\n  \
+         handled by arm 0 `GET /user/:id`\n\
+         \n\
+         GET /posts\n  \
+         expected: Some(\"posts\")\n     \
+         found: None\n  \
+         no arm handled the request\n\
+         \n\
+         /user\n  \
+         expected a method followed by a path, e.g. `GET /user/42`\n"
+    );
+}
+
+#[test]
+fn test_route_cases_report_the_arm_taken() {
+    let panic = std::panic::catch_unwind(|| {
+        let mut cases = RouteCases::new(conditional::route::ROUTES);
+        cases.check("GET /user/42", Some("json"), |method, path| {
+            conditional::route(method, path, "example.com", &[("accept", "text/html")])
+        });
+        cases.finish();
+    })
+    .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert_eq!(
+        message,
+        "1 of 1 route cases failed:\n\
+         \n\
+         GET /user/42\n  \
+         expected: Some(\"json\")\n     \
+         found: Some(\"user\")\n  \
+         handled by arm 2 `GET /user/*`\n"
+    );
+}
+
+#[test]
+fn test_route_cases_follow_dispatch_order() {
+    let routes: Vec<String> = specific::route::ROUTES
+        .iter()
+        .map(|route| format!("{} {}", route.arm(), route))
+        .collect();
+    assert_eq!(
+        routes,
+        [
+            "2 GET /files/index",
+            "1 GET /files/:name",
+            "0 GET /files/..:path"
+        ]
+    );
+
+    let panic = std::panic::catch_unwind(|| {
+        assert_routes!(specific::route, {
+            "GET /files/index" => "index",
+            "GET /files/readme" => "readme.md",
+            "GET /files/docs/readme" => "docs/readme",
+            "POST /files/index" => "index",
+        });
+    })
+    .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert_eq!(
+        message,
+        "2 of 4 route cases failed:\n\
+         \n\
+         GET /files/readme\n  \
+         expected: \"readme.md\"\n     \
+         found: \"readme\"\n  \
+         handled by arm 1 `GET /files/:name`\n\
+         \n\
+         POST /files/index\n  \
+         expected: \"index\"\n     \
+         found: \"not found\"\n  \
+         handled by the default arm\n"
+    );
+}
+
+#[test]
+fn test_route_cases_skip_declined_arms() {
+    let panic = std::panic::catch_unwind(|| {
+        assert_routes!(delegating::route, {
+            "GET /admin/status" => "users",
+            "GET /admin/users" => "status",
+        });
+    })
+    .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert_eq!(
+        message,
+        "2 of 2 route cases failed:\n\
+         \n\
+         GET /admin/status\n  \
+         expected: \"users\"\n     \
+         found: \"status\"\n  \
+         handled by arm 0 `_ /admin/..`\n\
+         \n\
+         GET /admin/users\n  \
+         expected: \"status\"\n     \
+         found: \"users\"\n  \
+         handled by arm 1 `GET /admin/users`\n"
+    );
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "tower")]
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Wake;
+use std::task::Waker;
+
+use http::Request;
+use http::Response;
+use http::StatusCode;
+use route_match::route;
+use route_match::route_opt;
+use route_match::tower::RouterService;
+use tower_service::Service;
+
+/// A waker for futures which never wait, so it's never woken
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+fn noop_waker() -> Waker {
+    Waker::from(Arc::new(NoopWaker))
+}
+
+/// Polls a future which doesn't wait on anything to completion
+fn ready<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(&noop_waker()))
+    {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("the future isn't ready"),
+    }
+}
+
+fn call<S, B>(service: &mut S, method: &str, uri: &str, body: B) -> S::Response
+where
+    S: Service<Request<B>, Error = Infallible>,
+{
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    assert!(matches!(service.poll_ready(&mut cx), Poll::Ready(Ok(()))));
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .body(body)
+        .unwrap();
+    match ready(service.call(request)) {
+        Ok(response) => response,
+        Err(infallible) => match infallible {},
+    }
+}
+
+/// The bounds axum's `fallback_service` and hyper-util's adapters need
+fn assert_servable<S: Service<Request<B>> + Clone + Send + 'static, B>(_: &S)
+where
+    S::Future: Send,
+{
+}
+
+#[test]
+fn test_router_service() {
+    let mut service = RouterService::new(|request: Request<Vec<u8>>| async move {
+        route! {
+            match &request {
+                GET /user/:id => Response::new(format!("user {}", id)),
+                POST /user => Response::new(format!("created {} bytes", request.body().len())),
+                _ => {
+                    let mut response = Response::new(String::new());
+                    *response.status_mut() = StatusCode::IM_A_TEAPOT;
+                    response
+                }
+            }
+        }
+    });
+    assert_servable(&service);
+
+    assert_eq!(
+        call(&mut service, "GET", "/user/42", vec![]).body(),
+        "user 42"
+    );
+    assert_eq!(
+        call(&mut service, "POST", "/user", vec![1, 2, 3]).body(),
+        "created 3 bytes"
+    );
+    assert_eq!(
+        call(&mut service, "GET", "/posts", vec![]).status(),
+        StatusCode::IM_A_TEAPOT
+    );
+}
+
+#[test]
+fn test_router_service_not_found() {
+    let mut service = RouterService::new(|request: Request<()>| async move {
+        route_opt! {
+            match request {
+                GET /user/:id => Response::new(format!("user {}", id)),
+            }
+        }
+    });
+    assert_servable(&service);
+
+    let response = call(&mut service, "GET", "/user/42", ());
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body(), "user 42");
+
+    let response = call(&mut service, "DELETE", "/user/42", ());
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.body(), "");
+}
@@ -0,0 +1,185 @@
+#![cfg(feature = "tracing")]
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Wake;
+use std::task::Waker;
+
+use route_match::route;
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span;
+use tracing::Event;
+use tracing::Metadata;
+use tracing::Subscriber;
+
+/// Records every span field and event message as `name=value` strings, and
+/// the events emitted while no span was entered
+#[derive(Clone, Default)]
+struct Recorder {
+    next_id: Arc<AtomicU64>,
+    entered: Arc<AtomicUsize>,
+    fields: Arc<Mutex<Vec<String>>>,
+    events: Arc<Mutex<Vec<String>>>,
+    unparented: Arc<Mutex<Vec<String>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push(format!("{}={:?}", field.name(), value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push(format!("{}={}", field.name(), value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        span.record(&mut FieldVisitor(&mut self.fields.lock().unwrap()));
+        span::Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _: &span::Id, values: &span::Record<'_>) {
+        values.record(&mut FieldVisitor(&mut self.fields.lock().unwrap()));
+    }
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        event.record(&mut FieldVisitor(&mut self.events.lock().unwrap()));
+        if self.entered.load(Ordering::SeqCst) == 0 && event.parent().is_none() {
+            event.record(&mut FieldVisitor(&mut self.unparented.lock().unwrap()));
+        }
+    }
+
+    fn enter(&self, _: &span::Id) {
+        self.entered.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn exit(&self, _: &span::Id) {
+        self.entered.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// A waker for futures which never wait, so it's never woken
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// Polls a future which doesn't wait on anything to completion
+fn ready<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(NoopWaker));
+    match future.as_mut().poll(&mut Context::from_waker(&waker)) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("the future isn't ready"),
+    }
+}
+
+fn route(method: &str, path: &str) -> String {
+    route! {
+        match (&method, &path) {
+            GET /user/:id => id.to_string(),
+            :method /files/..:rest => format!("{method} {rest}"),
+            _ => "none".to_string(),
+        }
+    }
+}
+
+#[test]
+fn test_span_records_route() {
+    let recorder = Recorder::default();
+    let result = tracing::subscriber::with_default(recorder.clone(), || route("GET", "/user/42"));
+
+    assert_eq!(&result, "42");
+    let fields = recorder.fields.lock().unwrap();
+    assert!(fields.contains(&"http.method=GET".to_string()));
+    assert!(fields.contains(&"http.route=/user/:id".to_string()));
+    assert!(fields.contains(&"id=42".to_string()));
+    assert!(recorder.events.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_span_records_rest_and_method_params() {
+    let recorder = Recorder::default();
+    let result =
+        tracing::subscriber::with_default(recorder.clone(), || route("PUT", "/files/a/b.txt"));
+
+    assert_eq!(&result, "PUT a/b.txt");
+    let fields = recorder.fields.lock().unwrap();
+    assert!(fields.contains(&"http.route=/files/..:rest".to_string()));
+    assert!(fields.contains(&"rest=a/b.txt".to_string()));
+    assert!(fields.contains(&"method=PUT".to_string()));
+}
+
+#[test]
+fn test_default_arm_emits_event() {
+    let recorder = Recorder::default();
+    let result = tracing::subscriber::with_default(recorder.clone(), || route("GET", "/nope"));
+
+    assert_eq!(&result, "none");
+    let events = recorder.events.lock().unwrap();
+    assert!(events.contains(&"http.path=/nope".to_string()));
+    assert!(!recorder
+        .fields
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|field| field.starts_with("http.route=")));
+}
+
+async fn load_user(id: &str) -> String {
+    tracing::info!(loading = id);
+    id.to_string()
+}
+
+async fn route_async(method: &str, path: &str) -> String {
+    route! {
+        match (&method, &path) {
+            GET /user/:id => load_user(id).await,
+            _ => "none".to_string(),
+        }
+    }
+}
+
+#[test]
+fn test_awaited_futures_are_instrumented() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let recorder = Recorder::default();
+    let result = tracing::subscriber::with_default(recorder.clone(), || {
+        let future = route_async("GET", "/user/7");
+        assert_send(&future);
+        ready(future)
+    });
+
+    assert_eq!(&result, "7");
+    assert!(recorder
+        .fields
+        .lock()
+        .unwrap()
+        .contains(&"http.route=/user/:id".to_string()));
+    assert!(recorder
+        .events
+        .lock()
+        .unwrap()
+        .contains(&"loading=7".to_string()));
+    assert!(recorder.unparented.lock().unwrap().is_empty());
+}
@@ -0,0 +1,71 @@
+use route_match::route;
+use route_match::route_pattern;
+use route_match::Method;
+use route_match::RoutePattern;
+
+const USER: RoutePattern = route_pattern!(GET /user/:id);
+
+#[test]
+fn test_method_enum() {
+    fn route(method: Method, path: &str) -> String {
+        route! {
+            match (method, path) {
+                GET /foo => "get".to_string(),
+                "PROPFIND" /foo => "propfind".to_string(),
+                :method /bar => format!("{} /bar", method),
+                use USER as captures => format!("user {}", captures.get("id").unwrap()),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    assert_eq!(&route(Method::Get, "/foo"), "get");
+    assert_eq!(&route(Method::Delete, "/foo"), "none");
+    assert_eq!(&route(Method::Patch, "/bar"), "PATCH /bar");
+    assert_eq!(&route(Method::Get, "/user/7"), "user 7");
+}
+
+#[test]
+fn test_str_methods() {
+    fn route(method: &str, path: &str) -> &'static str {
+        route! {
+            match (&method, &path) {
+                GET /foo => "get",
+                "PROPFIND" /foo => "propfind",
+                _ => "none",
+            }
+        }
+    }
+
+    assert_eq!(route("GET", "/foo"), "get");
+    assert_eq!(route("PROPFIND", "/foo"), "propfind");
+    assert_eq!(route("POST", "/foo"), "none");
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn test_http_method() {
+    fn route(method: &http::Method, path: &str) -> String {
+        route! {
+            #![methods(MKCOL)]
+            match (method, path) {
+                GET /foo => "get".to_string(),
+                DELETE /foo => "delete".to_string(),
+                "PROPFIND" /foo => "propfind".to_string(),
+                MKCOL /foo => "mkcol".to_string(),
+                :method /bar => format!("{} /bar", method),
+                _ => "none".to_string(),
+            }
+        }
+    }
+
+    let propfind = http::Method::from_bytes(b"PROPFIND").unwrap();
+    assert_eq!(&route(&http::Method::GET, "/foo"), "get");
+    assert_eq!(&route(&http::Method::DELETE, "/foo"), "delete");
+    assert_eq!(&route(&propfind, "/foo"), "propfind");
+    let mkcol = http::Method::from_bytes(b"MKCOL").unwrap();
+    assert_eq!(&route(&mkcol, "/foo"), "mkcol");
+    assert_eq!(&route(&http::Method::POST, "/foo"), "none");
+    assert_eq!(&route(&http::Method::PUT, "/bar"), "PUT /bar");
+    assert!(USER.matches(http::Method::GET.as_str(), "/user/1"));
+}
@@ -0,0 +1,11 @@
+use route_match::route;
+
+fn main() {
+    let (method, path) = ("GET", "/users/1/posts/2");
+    let _ = route! {
+        match (&method, &path) {
+            GET /users/:id/posts/:id => (),
+            _ => (),
+        }
+    };
+}
@@ -0,0 +1,11 @@
+use route_match::route;
+
+fn main() {
+    let (method, path) = ("GET", "/files/a/b");
+    let _ = route! {
+        match (&method, &path) {
+            GET /files/:r#type/:type => (),
+            _ => (),
+        }
+    };
+}
@@ -0,0 +1,11 @@
+use route_match::route;
+
+fn main() {
+    let (method, path) = ("GET", "/reports/1.json");
+    let _ = route! {
+        match (&method, &path) {
+            GET /reports/:id.{json,csv}/raw => (),
+            _ => (),
+        }
+    };
+}
@@ -0,0 +1,15 @@
+use route_match::route_opt;
+use route_match::Mount;
+
+fn tenants(_method: &str, _path: Mount) -> Option<&'static str> {
+    None
+}
+
+fn main() {
+    let (method, path) = ("GET", "/acme/orders");
+    let _ = route_opt! {
+        match (&method, &path) {
+            _ /:tenant/..:rest => mount tenants,
+        }
+    };
+}
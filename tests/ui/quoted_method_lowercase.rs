@@ -0,0 +1,11 @@
+use route_match::route;
+
+fn main() {
+    let (method, path) = ("GET", "/users");
+    let _ = route! {
+        match (&method, &path) {
+            "get" /users => (),
+            _ => (),
+        }
+    };
+}
@@ -0,0 +1,11 @@
+use route_match::route;
+
+fn main() {
+    let (method, path) = ("GET", "/a/b");
+    let _ = route! {
+        match (&method, &path) {
+            GET /"" => (),
+            _ => (),
+        }
+    };
+}
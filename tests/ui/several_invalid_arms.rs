@@ -0,0 +1,14 @@
+use route_match::route;
+
+fn main() {
+    let (method, path) = ("GET", "/users/1/posts/2");
+    let _ = route! {
+        match (&method, &path) {
+            GTE /users/:id => (),
+            GET /users/:id/posts/:id => (),
+            POST /users/:fn => (),
+            DELETE api.example.com/users/:id => (),
+            _ => (),
+        }
+    };
+}